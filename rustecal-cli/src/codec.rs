@@ -0,0 +1,115 @@
+//! Converting payloads to and from text, based on their `DataTypeInfo`.
+//!
+//! JSON, CBOR and MessagePack payloads are shown as JSON. Protobuf payloads
+//! are decoded with the descriptor announced with the type (a serialized
+//! `FileDescriptorSet`) and shown as JSON as well; in the other direction,
+//! JSON is encoded into the protobuf message.
+
+use prost_reflect::prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use rustecal_core::types::DataTypeInfo;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Number of payload bytes shown by [`hex_dump`].
+const HEX_DUMP_LIMIT: usize = 256;
+
+/// Converts payloads, caching the protobuf descriptors it has parsed.
+#[derive(Default)]
+pub struct Codec {
+    descriptors: HashMap<String, MessageDescriptor>,
+}
+
+impl Codec {
+    /// Renders `bytes` as text.
+    ///
+    /// Payloads that cannot be decoded are shown as hex dump, preceded by the reason.
+    pub fn render(&mut self, datatype: &DataTypeInfo, bytes: &[u8]) -> String {
+        match self.decode(datatype, bytes) {
+            Ok(text) => text,
+            Err(e) => format!("({})\n{}", e, hex_dump(bytes)),
+        }
+    }
+
+    /// Decodes `bytes` into text according to the encoding of `datatype`.
+    pub fn decode(&mut self, datatype: &DataTypeInfo, bytes: &[u8]) -> Result<String, String> {
+        let value: Value = match datatype.encoding.as_str() {
+            "utf-8" | "string" => {
+                return std::str::from_utf8(bytes)
+                    .map(str::to_string)
+                    .map_err(|e| format!("invalid UTF-8: {}", e))
+            }
+            "json" => serde_json::from_slice(bytes).map_err(|e| format!("invalid JSON: {}", e))?,
+            "cbor" => serde_cbor::from_slice(bytes).map_err(|e| format!("invalid CBOR: {}", e))?,
+            "msgpack" => rmp_serde::from_slice(bytes).map_err(|e| format!("invalid MessagePack: {}", e))?,
+            "proto" => {
+                let descriptor = self.descriptor(datatype)?;
+                let message = DynamicMessage::decode(descriptor, bytes)
+                    .map_err(|e| format!("invalid {}: {}", datatype.type_name, e))?;
+                serde_json::to_value(&message).map_err(|e| e.to_string())?
+            }
+            other => return Err(format!("no decoder for encoding '{}'", other)),
+        };
+        serde_json::to_string_pretty(&value).map_err(|e| e.to_string())
+    }
+
+    /// Encodes `text` according to the encoding of `datatype`.
+    ///
+    /// `text` is sent as is for UTF-8 and raw topics and must be JSON for
+    /// all other encodings.
+    pub fn encode(&mut self, datatype: &DataTypeInfo, text: &str) -> Result<Vec<u8>, String> {
+        let json = || serde_json::from_str::<Value>(text).map_err(|e| format!("invalid JSON: {}", e));
+        match datatype.encoding.as_str() {
+            "" | "utf-8" | "string" | "raw" => Ok(text.as_bytes().to_vec()),
+            "json" => serde_json::to_vec(&json()?).map_err(|e| e.to_string()),
+            "cbor" => serde_cbor::to_vec(&json()?).map_err(|e| e.to_string()),
+            "msgpack" => rmp_serde::to_vec(&json()?).map_err(|e| e.to_string()),
+            "proto" => {
+                let descriptor = self.descriptor(datatype)?;
+                let mut deserializer = serde_json::Deserializer::from_str(text);
+                let message = DynamicMessage::deserialize(descriptor, &mut deserializer)
+                    .map_err(|e| format!("invalid {}: {}", datatype.type_name, e))?;
+                Ok(message.encode_to_vec())
+            }
+            other => Err(format!("no encoder for encoding '{}'", other)),
+        }
+    }
+
+    /// Returns the descriptor of the protobuf type of `datatype`.
+    fn descriptor(&mut self, datatype: &DataTypeInfo) -> Result<MessageDescriptor, String> {
+        if let Some(descriptor) = self.descriptors.get(&datatype.type_name) {
+            return Ok(descriptor.clone());
+        }
+        if datatype.descriptor.is_empty() {
+            return Err(format!("no descriptor for '{}'", datatype.type_name));
+        }
+
+        let pool = DescriptorPool::decode(datatype.descriptor.as_slice())
+            .map_err(|e| format!("invalid descriptor of '{}': {}", datatype.type_name, e))?;
+        let descriptor = pool
+            .get_message_by_name(&datatype.type_name)
+            .ok_or_else(|| format!("'{}' is missing in its descriptor", datatype.type_name))?;
+        self.descriptors.insert(datatype.type_name.clone(), descriptor.clone());
+        Ok(descriptor)
+    }
+}
+
+/// Formats up to [`HEX_DUMP_LIMIT`] bytes as offset, hex and ASCII columns.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut lines: Vec<String> = bytes[..bytes.len().min(HEX_DUMP_LIMIT)]
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+                .collect();
+            format!("{:08x}  {:<47}  {}", i * 16, hex.join(" "), ascii)
+        })
+        .collect();
+    if bytes.len() > HEX_DUMP_LIMIT {
+        lines.push(format!("... {} more bytes", bytes.len() - HEX_DUMP_LIMIT));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,173 @@
+//! `ecal-rs`: inspect and interact with eCAL topics and services from the command line.
+
+mod codec;
+mod services;
+mod topics;
+
+use clap::{Parser, Subcommand};
+use rustecal_core::monitoring::Monitoring;
+use rustecal_core::{Ecal, EcalComponents};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Inspect and interact with eCAL topics and services.
+#[derive(Parser)]
+#[command(name = "ecal-rs", version)]
+struct Cli {
+    /// Milliseconds to wait for other eCAL processes to be discovered
+    #[arg(long, global = true, value_name = "MS", default_value_t = 1500)]
+    discovery: u64,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Inspect all topics
+    Topics {
+        #[command(subcommand)]
+        command: TopicsCommand,
+    },
+    /// Receive from or publish on a topic
+    Topic {
+        #[command(subcommand)]
+        command: TopicCommand,
+    },
+    /// Inspect all services
+    Services {
+        #[command(subcommand)]
+        command: ServicesCommand,
+    },
+    /// Call a service
+    Service {
+        #[command(subcommand)]
+        command: ServiceCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum TopicsCommand {
+    /// List all publishers and subscribers
+    List,
+}
+
+#[derive(Subcommand)]
+enum TopicCommand {
+    /// Print the messages on a topic, decoded by their encoding
+    Echo {
+        name: String,
+        /// Exit after this many messages
+        #[arg(short = 'n', long)]
+        count: Option<u64>,
+    },
+    /// Print the rate of a topic
+    Hz {
+        name: String,
+        /// Number of messages to compute the rate over
+        #[arg(short, long, default_value_t = 100)]
+        window: usize,
+    },
+    /// Print the bandwidth of a topic
+    Bw {
+        name: String,
+        /// Number of messages to compute the bandwidth over
+        #[arg(short, long, default_value_t = 100)]
+        window: usize,
+    },
+    /// Publish a message given as text (JSON for json, cbor, msgpack and proto topics)
+    Pub {
+        name: String,
+        data: String,
+        /// Encoding; defaults to the encoding of the topic's other participants, or utf-8
+        #[arg(short, long)]
+        encoding: Option<String>,
+        /// Type name, if the topic is not known yet
+        #[arg(short, long = "type")]
+        type_name: Option<String>,
+        /// Publish repeatedly at this rate in Hz, until Ctrl+C or --count
+        #[arg(short, long)]
+        rate: Option<f64>,
+        /// Number of messages to publish
+        #[arg(short = 'n', long)]
+        count: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ServicesCommand {
+    /// List the methods of all service servers
+    List,
+}
+
+#[derive(Subcommand)]
+enum ServiceCommand {
+    /// Call a method on all servers of a service and print the responses
+    Call {
+        service: String,
+        method: String,
+        /// Request given as text (JSON for json, cbor, msgpack and proto methods)
+        #[arg(default_value = "")]
+        request: String,
+        /// Timeout in milliseconds
+        #[arg(short, long, default_value_t = 1000)]
+        timeout: i32,
+    },
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = running.clone();
+        ctrlc::set_handler(move || running.store(false, Ordering::SeqCst))?;
+    }
+
+    Ecal::initialize(Some("ecal-rs"), EcalComponents::DEFAULT | EcalComponents::MONITORING)?;
+    let result = run(cli, &running);
+    Ecal::finalize();
+    result
+}
+
+fn run(cli: Cli, running: &AtomicBool) -> Result<(), Box<dyn std::error::Error>> {
+    // the monitoring layer needs a moment to learn about other processes
+    std::thread::sleep(Duration::from_millis(cli.discovery));
+    let snapshot = Monitoring::get_snapshot()?;
+
+    match cli.command {
+        Command::Topics { command: TopicsCommand::List } => topics::list(&snapshot),
+        Command::Topic { command } => match command {
+            TopicCommand::Echo { name, count } => topics::echo(&snapshot, &name, count, running)?,
+            TopicCommand::Hz { name, window } => topics::hz(&name, window, running)?,
+            TopicCommand::Bw { name, window } => topics::bw(&name, window, running)?,
+            TopicCommand::Pub {
+                name,
+                data,
+                encoding,
+                type_name,
+                rate,
+                count,
+            } => {
+                let options = topics::PublishOptions {
+                    encoding,
+                    type_name,
+                    rate,
+                    count,
+                };
+                topics::publish(&snapshot, &name, &data, options, running)?
+            }
+        },
+        Command::Services { command: ServicesCommand::List } => services::list(&snapshot),
+        Command::Service {
+            command: ServiceCommand::Call {
+                service,
+                method,
+                request,
+                timeout,
+            },
+        } => services::call(&snapshot, &service, &method, &request, timeout)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//! `services` and `service` subcommands.
+
+use crate::codec::Codec;
+use rustecal_core::core_types::monitoring::{MethodInfo, MonitoringSnapshot};
+use rustecal_core::types::DataTypeInfo;
+use rustecal_service::{ClientMethod, ServiceClient, ServiceRequest};
+use std::time::Duration;
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Prints the methods of all service servers, sorted by service name.
+pub fn list(snapshot: &MonitoringSnapshot) {
+    let mut servers: Vec<_> = snapshot.servers.iter().collect();
+    servers.sort_by(|a, b| a.service_name.cmp(&b.service_name));
+
+    println!(
+        "{:<25} {:<20} {:<30} {:<30} {:<20} HOST",
+        "SERVICE", "METHOD", "REQUEST", "RESPONSE", "PROCESS"
+    );
+    for server in servers {
+        for method in &server.methods {
+            println!(
+                "{:<25} {:<20} {:<30} {:<30} {:<20} {}",
+                server.service_name,
+                method.method_name,
+                describe(&method.request_type),
+                describe(&method.response_type),
+                format!("{} ({})", server.process_name, server.process_id),
+                server.host_name,
+            );
+        }
+    }
+}
+
+fn describe(datatype: &DataTypeInfo) -> String {
+    if datatype.encoding.is_empty() {
+        datatype.type_name.clone()
+    } else {
+        format!("{} ({})", datatype.type_name, datatype.encoding)
+    }
+}
+
+/// Calls `method_name` of `service_name` on all servers and prints the responses.
+///
+/// The request is encoded and the responses are decoded with the types the
+/// servers announce for the method.
+pub fn call(
+    snapshot: &MonitoringSnapshot,
+    service_name: &str,
+    method_name: &str,
+    request: &str,
+    timeout_ms: i32,
+) -> CliResult {
+    let method = snapshot
+        .servers_by_name(service_name)
+        .flat_map(|s| &s.methods)
+        .find(|m| m.method_name == method_name);
+    let client_method = match method {
+        Some(MethodInfo { request_type, response_type, .. }) => {
+            ClientMethod::with_types(method_name, request_type.clone(), response_type.clone())
+        }
+        None => ClientMethod::new(method_name),
+    };
+
+    let mut codec = Codec::default();
+    let payload = codec.encode(&client_method.request_type, request)?;
+    let response_type = client_method.response_type.clone();
+
+    let client = ServiceClient::with_methods(service_name, vec![client_method])?;
+    if !client.wait_for_server(Duration::from_millis(timeout_ms.max(0) as u64)) {
+        return Err(format!("no server available for service '{}'", service_name).into());
+    }
+
+    let responses = client.call(method_name, ServiceRequest { payload }, Some(timeout_ms))?;
+    for response in responses {
+        let server = &response.server_id.service_id;
+        println!("--- {} ({}) | {:?}", server.host_name, server.process_id, response.call_state);
+        match response.error_msg {
+            Some(error) if !response.is_success() => println!("error: {}", error),
+            _ => println!("{}", codec.render(&response_type, &response.payload)),
+        }
+    }
+    Ok(())
+}
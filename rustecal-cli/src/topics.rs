@@ -0,0 +1,263 @@
+//! `topics` and `topic` subcommands.
+
+use crate::codec::Codec;
+use rustecal_core::core_types::monitoring::MonitoringSnapshot;
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::{Publisher, RawReceived, Subscriber};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Prints all publishers and subscribers, sorted by topic name.
+pub fn list(snapshot: &MonitoringSnapshot) {
+    let mut topics: Vec<_> = snapshot.topics().collect();
+    topics.sort_by(|a, b| (&a.topic_name, &a.direction).cmp(&(&b.topic_name, &b.direction)));
+
+    println!(
+        "{:<30} {:<10} {:<30} {:<8} {:>9}  {:<20} HOST",
+        "TOPIC", "DIRECTION", "TYPE", "ENCODING", "RATE [Hz]", "PROCESS"
+    );
+    for topic in topics {
+        println!(
+            "{:<30} {:<10} {:<30} {:<8} {:>9.1}  {:<20} {}",
+            topic.topic_name,
+            topic.direction,
+            topic.data_type.type_name,
+            topic.data_type.encoding,
+            // eCAL reports the frequency in mHz
+            topic.data_frequency as f64 / 1000.0,
+            format!("{} ({})", topic.process_name, topic.process_id),
+            topic.host_name,
+        );
+    }
+}
+
+/// Returns the datatype announced for `topic_name`, preferring publishers.
+pub fn datatype_of(snapshot: &MonitoringSnapshot, topic_name: &str) -> Option<DataTypeInfo> {
+    snapshot.topics_by_name(topic_name).next().map(|t| t.data_type.clone())
+}
+
+/// Prints every message on `topic_name`, decoded according to its datatype.
+pub fn echo(snapshot: &MonitoringSnapshot, topic_name: &str, count: Option<u64>, running: &AtomicBool) -> CliResult {
+    let datatype = datatype_of(snapshot, topic_name).unwrap_or_else(empty_datatype);
+    let received = Arc::new(AtomicU64::new(0));
+    let codec = Mutex::new(Codec::default());
+
+    let _subscriber = {
+        let received = received.clone();
+        Subscriber::with_callback(topic_name, datatype, move |msg: RawReceived| {
+            // ignore messages arriving after the requested count
+            if count.is_some_and(|count| received.load(Ordering::SeqCst) >= count) {
+                return;
+            }
+            let text = codec.lock().unwrap_or_else(|e| e.into_inner()).render(&msg.datatype, msg.bytes);
+            println!(
+                "--- {} | {} ({}) | clock {} | {} bytes\n{}",
+                msg.topic_id.topic_name,
+                msg.datatype.type_name,
+                msg.datatype.encoding,
+                msg.clock,
+                msg.bytes.len(),
+                text
+            );
+            received.fetch_add(1, Ordering::SeqCst);
+        })?
+    };
+
+    while running.load(Ordering::SeqCst) && count.is_none_or(|count| received.load(Ordering::SeqCst) < count) {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    Ok(())
+}
+
+/// Arrival times and sizes of the most recent messages on a topic.
+struct Samples {
+    window: usize,
+    samples: VecDeque<(Instant, usize)>,
+    total: u64,
+}
+
+impl Samples {
+    /// Subscribes to `topic_name`, keeping the last `window` messages.
+    fn subscribe(topic_name: &str, window: usize) -> Result<(Subscriber, Arc<Mutex<Self>>), String> {
+        let samples = Arc::new(Mutex::new(Samples {
+            window: window.max(2),
+            samples: VecDeque::new(),
+            total: 0,
+        }));
+        let subscriber = {
+            let samples = samples.clone();
+            Subscriber::with_callback(topic_name, empty_datatype(), move |msg: RawReceived| {
+                samples.lock().unwrap_or_else(|e| e.into_inner()).push(msg.bytes.len());
+            })?
+        };
+        Ok((subscriber, samples))
+    }
+
+    fn push(&mut self, size: usize) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((Instant::now(), size));
+        self.total += 1;
+    }
+
+    /// Time from the first to the last sample.
+    fn span(&self) -> Option<Duration> {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(first), Some(last)) if self.samples.len() >= 2 => Some(last.0 - first.0),
+            _ => None,
+        }
+    }
+}
+
+/// Prints the rate of `topic_name` every second, computed over the last `window` messages.
+pub fn hz(topic_name: &str, window: usize, running: &AtomicBool) -> CliResult {
+    let (_subscriber, samples) = Samples::subscribe(topic_name, window)?;
+    report_every_second(running, &samples, |samples| {
+        let span = samples.span().filter(|span| !span.is_zero())?;
+        let intervals: Vec<f64> = samples
+            .samples
+            .iter()
+            .zip(samples.samples.iter().skip(1))
+            .map(|(a, b)| (b.0 - a.0).as_secs_f64())
+            .collect();
+        let mean = span.as_secs_f64() / intervals.len() as f64;
+        let variance = intervals.iter().map(|i| (i - mean).powi(2)).sum::<f64>() / intervals.len() as f64;
+        let min = intervals.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = intervals.iter().copied().fold(0.0, f64::max);
+        Some(format!(
+            "average rate: {:.3} Hz\n    min: {:.4}s max: {:.4}s std dev: {:.5}s window: {}",
+            1.0 / mean,
+            min,
+            max,
+            variance.sqrt(),
+            samples.samples.len()
+        ))
+    })
+}
+
+/// Prints the bandwidth of `topic_name` every second, computed over the last `window` messages.
+pub fn bw(topic_name: &str, window: usize, running: &AtomicBool) -> CliResult {
+    let (_subscriber, samples) = Samples::subscribe(topic_name, window)?;
+    report_every_second(running, &samples, |samples| {
+        let span = samples.span().filter(|span| !span.is_zero())?;
+        let sizes = samples.samples.iter().map(|s| s.1);
+        // the first message only marks the start of the span
+        let bytes: usize = sizes.clone().skip(1).sum();
+        Some(format!(
+            "{}/s from {} messages\n    mean: {} min: {} max: {}",
+            format_bytes(bytes as f64 / span.as_secs_f64()),
+            samples.samples.len(),
+            format_bytes(sizes.clone().sum::<usize>() as f64 / samples.samples.len() as f64),
+            format_bytes(sizes.clone().min().unwrap_or(0) as f64),
+            format_bytes(sizes.max().unwrap_or(0) as f64),
+        ))
+    })
+}
+
+/// Prints `report(samples)` once a second while new messages arrive.
+fn report_every_second<F>(running: &AtomicBool, samples: &Mutex<Samples>, report: F) -> CliResult
+where
+    F: Fn(&Samples) -> Option<String>,
+{
+    let mut last_total = 0;
+    let mut next = Instant::now() + Duration::from_secs(1);
+    while running.load(Ordering::SeqCst) {
+        if Instant::now() < next {
+            std::thread::sleep(Duration::from_millis(20));
+            continue;
+        }
+        next += Duration::from_secs(1);
+
+        let samples = samples.lock().unwrap_or_else(|e| e.into_inner());
+        if samples.total == last_total {
+            println!("no new messages");
+            continue;
+        }
+        last_total = samples.total;
+        if let Some(line) = report(&samples) {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+fn format_bytes(bytes: f64) -> String {
+    match bytes {
+        b if b >= 1024.0 * 1024.0 => format!("{:.2} MB", b / (1024.0 * 1024.0)),
+        b if b >= 1024.0 => format!("{:.2} KB", b / 1024.0),
+        b => format!("{:.0} B", b),
+    }
+}
+
+/// Options of `topic pub`.
+pub struct PublishOptions {
+    pub encoding: Option<String>,
+    pub type_name: Option<String>,
+    pub rate: Option<f64>,
+    pub count: Option<u64>,
+}
+
+/// Publishes `text` on `topic_name`, encoded like the topic's existing
+/// publishers and subscribers unless an encoding is given.
+pub fn publish(
+    snapshot: &MonitoringSnapshot,
+    topic_name: &str,
+    text: &str,
+    options: PublishOptions,
+    running: &AtomicBool,
+) -> CliResult {
+    if options.rate.is_some_and(|rate| !(rate > 0.0 && rate.is_finite())) {
+        return Err("the rate must be greater than zero".into());
+    }
+
+    let known = datatype_of(snapshot, topic_name);
+    let datatype = match (options.encoding, known) {
+        (None, Some(known)) => known,
+        (Some(encoding), Some(known)) if known.encoding == encoding => known,
+        (encoding, _) => DataTypeInfo {
+            encoding: encoding.unwrap_or_else(|| "utf-8".to_string()),
+            type_name: options.type_name.unwrap_or_else(|| "string".to_string()),
+            descriptor: Vec::new(),
+        },
+    };
+    let payload = Codec::default().encode(&datatype, text)?;
+
+    let publisher = Publisher::new(topic_name, datatype.clone())?;
+    println!("Publishing on '{}' as {} ({})", topic_name, datatype.type_name, datatype.encoding);
+
+    // give subscribers a moment to connect, so the first message is not lost
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while publisher.get_subscriber_count() == 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let count = match options.rate {
+        Some(_) => options.count,
+        None => Some(options.count.unwrap_or(1)),
+    };
+    let period = options.rate.map(|rate| Duration::from_secs_f64(1.0 / rate));
+
+    let mut sent = 0;
+    while running.load(Ordering::SeqCst) && count.is_none_or(|count| sent < count) {
+        publisher.send(&payload);
+        sent += 1;
+        if let Some(period) = period {
+            std::thread::sleep(period);
+        }
+    }
+    println!("Published {} messages", sent);
+    Ok(())
+}
+
+fn empty_datatype() -> DataTypeInfo {
+    DataTypeInfo {
+        encoding: String::new(),
+        type_name: String::new(),
+        descriptor: Vec::new(),
+    }
+}
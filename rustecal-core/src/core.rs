@@ -7,11 +7,13 @@
 //! - [`Ecal::initialize`] to start the middleware
 //! - [`Ecal::finalize`] to shut it down
 //! - [`Ecal::ok`] to query if eCAL is currently running
+//! - [`Ecal::shutdown_requested`] to detect a shutdown request from another process
 //! - [`Ecal::is_initialized`] and [`Ecal::is_component_initialized`] for introspection
 //! - [`Ecal::version_string`], [`Ecal::version_date_string`] and [`Ecal::version_struct`] for version info
 //!
 //! Typically, you will call [`Ecal::initialize`] once at the beginning of your
-//! application and [`Ecal::finalize`] at shutdown.
+//! application and [`Ecal::finalize`] at shutdown. Alternatively,
+//! [`EcalGuard`](crate::EcalGuard) finalizes eCAL automatically when dropped.
 
 use std::ffi::{CStr, CString};
 use std::ptr;
@@ -56,6 +58,9 @@ impl Ecal {
     /// Finalizes and shuts down the eCAL runtime system.
     ///
     /// After calling this, all publishers, subscribers, and services are invalidated.
+    ///
+    /// Do not call this while an [`EcalGuard`](crate::EcalGuard) is alive; the
+    /// guard finalizes eCAL itself once the last clone is dropped.
     pub fn finalize() {
         unsafe {
             rustecal_sys::eCAL_Finalize();
@@ -63,10 +68,24 @@ impl Ecal {
     }
 
     /// Returns `true` if the eCAL system is currently operational.
+    ///
+    /// This turns `false` once another process requests the shutdown of this
+    /// one (see [`crate::util::Util`]), so a `while Ecal::ok() { ... }` loop
+    /// terminates gracefully.
     pub fn ok() -> bool {
         unsafe { rustecal_sys::eCAL_Ok() != 0 }
     }
 
+    /// Returns `true` if eCAL is initialized but another process has requested
+    /// the shutdown of this one.
+    ///
+    /// Unlike [`Ecal::ok`], this stays `false` before initialization and after
+    /// [`Ecal::finalize`], so it can be used to tell a remote shutdown request
+    /// apart from a regular local shutdown.
+    pub fn shutdown_requested() -> bool {
+        Self::is_initialized() && !Self::ok()
+    }
+
     /// Returns `true` if *any* eCAL components have been initialized.
     pub fn is_initialized() -> bool {
         unsafe { rustecal_sys::eCAL_IsInitialized() != 0 }
@@ -1,4 +1,5 @@
 //! Common eCAL types shared across pubsub and service layers.
 
 pub mod monitoring;
+pub mod monitoring_diff;
 pub mod logging;
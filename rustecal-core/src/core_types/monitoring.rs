@@ -16,7 +16,7 @@ fn cstr(ptr: *const c_char) -> String {
 }
 
 /// Transport layer type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TransportLayerType {
     None,
     UdpMulticast,
@@ -26,7 +26,7 @@ pub enum TransportLayerType {
 }
 
 /// A single transport layer entry.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TransportLayer {
     pub transport_type: TransportLayerType,
     pub version: i32,
@@ -34,7 +34,7 @@ pub struct TransportLayer {
 }
 
 /// Full snapshot of monitoring information from the eCAL runtime.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MonitoringSnapshot {
     pub processes: Vec<ProcessInfo>,
     pub publishers: Vec<TopicInfo>,
@@ -43,8 +43,58 @@ pub struct MonitoringSnapshot {
     pub clients: Vec<ClientInfo>,
 }
 
+impl MonitoringSnapshot {
+    /// Returns all publishers and subscribers.
+    pub fn topics(&self) -> impl Iterator<Item = &TopicInfo> {
+        self.publishers.iter().chain(&self.subscribers)
+    }
+
+    /// Returns the publishers and subscribers of the topic `topic_name`.
+    pub fn topics_by_name<'a>(&'a self, topic_name: &'a str) -> impl Iterator<Item = &'a TopicInfo> {
+        self.topics().filter(move |t| t.topic_name == topic_name)
+    }
+
+    /// Returns the publishers and subscribers whose datatype has `encoding`.
+    pub fn topics_by_encoding<'a>(&'a self, encoding: &'a str) -> impl Iterator<Item = &'a TopicInfo> {
+        self.topics().filter(move |t| t.data_type.encoding == encoding)
+    }
+
+    /// Returns the publishers and subscribers running on `host_name`.
+    pub fn topics_by_host<'a>(&'a self, host_name: &'a str) -> impl Iterator<Item = &'a TopicInfo> {
+        self.topics().filter(move |t| t.host_name == host_name)
+    }
+
+    /// Returns the publishers whose topic has no subscriber on any host.
+    pub fn publishers_without_subscribers(&self) -> impl Iterator<Item = &TopicInfo> {
+        self.publishers
+            .iter()
+            .filter(|p| !self.subscribers.iter().any(|s| s.topic_name == p.topic_name))
+    }
+
+    /// Returns the processes initialized with the unit name `unit_name`.
+    pub fn processes_by_unit_name<'a>(&'a self, unit_name: &'a str) -> impl Iterator<Item = &'a ProcessInfo> {
+        self.processes.iter().filter(move |p| p.unit_name == unit_name)
+    }
+
+    /// Returns the servers of the service `service_name`.
+    pub fn servers_by_name<'a>(&'a self, service_name: &'a str) -> impl Iterator<Item = &'a ServerInfo> {
+        self.servers.iter().filter(move |s| s.service_name == service_name)
+    }
+
+    /// Returns the processes running a server of the service `service_name`.
+    ///
+    /// Servers whose process is not part of the snapshot are left out; use
+    /// [`MonitoringSnapshot::servers_by_name`] for their host and process id.
+    pub fn processes_serving<'a>(&'a self, service_name: &'a str) -> impl Iterator<Item = &'a ProcessInfo> {
+        self.processes.iter().filter(move |p| {
+            self.servers_by_name(service_name)
+                .any(|s| s.host_name == p.host_name && s.process_id == p.process_id)
+        })
+    }
+}
+
 /// A monitored eCAL process.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProcessInfo {
     pub registration_clock: i32,
     pub host_name: String,
@@ -65,7 +115,7 @@ pub struct ProcessInfo {
 }
 
 /// A monitored topic (publisher or subscriber).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TopicInfo {
     pub registration_clock: i32,
     pub host_name: String,
@@ -88,7 +138,7 @@ pub struct TopicInfo {
 }
 
 /// A method entry of a service.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MethodInfo {
     pub method_name: String,
     pub request_type: DataTypeInfo,
@@ -97,7 +147,7 @@ pub struct MethodInfo {
 }
 
 /// A monitored service server.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ServerInfo {
     pub registration_clock: i32,
     pub host_name: String,
@@ -113,7 +163,7 @@ pub struct ServerInfo {
 }
 
 /// A monitored service client.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClientInfo {
     pub registration_clock: i32,
     pub host_name: String,
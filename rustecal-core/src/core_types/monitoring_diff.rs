@@ -0,0 +1,385 @@
+//! Differences between two monitoring snapshots.
+//!
+//! Entities are matched by identity: processes by host name and process id,
+//! topics by topic id and services by service id. An entity counts as changed
+//! if any of its fields differ, except for counters that advance on their own:
+//! the registration clock, which advances with every registration cycle, the
+//! `data_id`, `data_clock` and `data_frequency` of topics, which change with
+//! every message, and the `call_count` of service methods. Read those from the
+//! next snapshot directly.
+
+use crate::core_types::monitoring::{ClientInfo, MethodInfo, MonitoringSnapshot, ProcessInfo, ServerInfo, TopicInfo};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// An entity present in both snapshots whose state changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changed<T> {
+    pub previous: T,
+    pub next: T,
+}
+
+/// The added, removed and changed entities of one kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityDiff<T> {
+    /// Entities only in the next snapshot.
+    pub added: Vec<T>,
+    /// Entities only in the previous snapshot.
+    pub removed: Vec<T>,
+    /// Entities in both snapshots that differ.
+    pub changed: Vec<Changed<T>>,
+}
+
+impl<T> Default for EntityDiff<T> {
+    fn default() -> Self {
+        Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        }
+    }
+}
+
+impl<T> EntityDiff<T> {
+    /// Returns `true` if nothing was added, removed or changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Differences between two [`MonitoringSnapshot`]s, per kind of entity.
+///
+/// Counters that advance on their own, like the data clock of an active
+/// publisher, do not make an entity count as changed; see the
+/// [module documentation](self).
+///
+/// # Example
+///
+/// ```no_run
+/// use rustecal_core::core_types::monitoring::MonitoringSnapshot;
+/// use rustecal_core::monitoring::Monitoring;
+///
+/// let mut previous = Monitoring::get_snapshot().unwrap();
+/// loop {
+///     std::thread::sleep(std::time::Duration::from_secs(1));
+///     let next = Monitoring::get_snapshot().unwrap();
+///     let diff = MonitoringSnapshot::diff(&previous, &next);
+///     for publisher in &diff.publishers.added {
+///         println!("new publisher on '{}'", publisher.topic_name);
+///     }
+///     previous = next;
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonitoringDiff {
+    pub processes: EntityDiff<ProcessInfo>,
+    pub publishers: EntityDiff<TopicInfo>,
+    pub subscribers: EntityDiff<TopicInfo>,
+    pub servers: EntityDiff<ServerInfo>,
+    pub clients: EntityDiff<ClientInfo>,
+}
+
+impl MonitoringDiff {
+    /// Returns `true` if the snapshots are equal, counters aside.
+    pub fn is_empty(&self) -> bool {
+        self.processes.is_empty()
+            && self.publishers.is_empty()
+            && self.subscribers.is_empty()
+            && self.servers.is_empty()
+            && self.clients.is_empty()
+    }
+}
+
+impl MonitoringSnapshot {
+    /// Compares two snapshots and returns what was added, removed and changed
+    /// from `previous` to `next`.
+    ///
+    /// Added and changed entities are listed in the order of `next`, removed
+    /// ones in the order of `previous`.
+    pub fn diff(previous: &MonitoringSnapshot, next: &MonitoringSnapshot) -> MonitoringDiff {
+        MonitoringDiff {
+            processes: diff_entities(&previous.processes, &next.processes),
+            publishers: diff_entities(&previous.publishers, &next.publishers),
+            subscribers: diff_entities(&previous.subscribers, &next.subscribers),
+            servers: diff_entities(&previous.servers, &next.servers),
+            clients: diff_entities(&previous.clients, &next.clients),
+        }
+    }
+}
+
+/// An entity that can be matched between snapshots.
+trait Entity: Clone {
+    type Key<'a>: Eq + Hash
+    where
+        Self: 'a;
+
+    fn key(&self) -> Self::Key<'_>;
+
+    /// Compares all fields except the counters that advance on their own.
+    fn same_state(&self, other: &Self) -> bool;
+}
+
+impl Entity for ProcessInfo {
+    type Key<'a> = (&'a str, i32);
+
+    fn key(&self) -> Self::Key<'_> {
+        (&self.host_name, self.process_id)
+    }
+
+    fn same_state(&self, other: &Self) -> bool {
+        *self == Self { registration_clock: self.registration_clock, ..other.clone() }
+    }
+}
+
+impl Entity for TopicInfo {
+    type Key<'a> = i64;
+
+    fn key(&self) -> i64 {
+        self.topic_id
+    }
+
+    fn same_state(&self, other: &Self) -> bool {
+        *self
+            == Self {
+                registration_clock: self.registration_clock,
+                data_id: self.data_id,
+                data_clock: self.data_clock,
+                data_frequency: self.data_frequency,
+                ..other.clone()
+            }
+    }
+}
+
+impl Entity for ServerInfo {
+    type Key<'a> = i64;
+
+    fn key(&self) -> i64 {
+        self.service_id
+    }
+
+    fn same_state(&self, other: &Self) -> bool {
+        same_methods(&self.methods, &other.methods)
+            && *self
+                == Self {
+                    registration_clock: self.registration_clock,
+                    methods: self.methods.clone(),
+                    ..other.clone()
+                }
+    }
+}
+
+impl Entity for ClientInfo {
+    type Key<'a> = i64;
+
+    fn key(&self) -> i64 {
+        self.service_id
+    }
+
+    fn same_state(&self, other: &Self) -> bool {
+        same_methods(&self.methods, &other.methods)
+            && *self
+                == Self {
+                    registration_clock: self.registration_clock,
+                    methods: self.methods.clone(),
+                    ..other.clone()
+                }
+    }
+}
+
+/// Compares two method lists, ignoring the call counts.
+fn same_methods(a: &[MethodInfo], b: &[MethodInfo]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| *a == MethodInfo { call_count: a.call_count, ..b.clone() })
+}
+
+fn diff_entities<T: Entity>(previous: &[T], next: &[T]) -> EntityDiff<T> {
+    let previous_by_key: HashMap<T::Key<'_>, &T> = previous.iter().map(|e| (e.key(), e)).collect();
+    let next_by_key: HashMap<T::Key<'_>, &T> = next.iter().map(|e| (e.key(), e)).collect();
+
+    let mut diff = EntityDiff::default();
+    for entity in next {
+        match previous_by_key.get(&entity.key()) {
+            None => diff.added.push(entity.clone()),
+            Some(&before) if !before.same_state(entity) => diff.changed.push(Changed {
+                previous: before.clone(),
+                next: entity.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    diff.removed = previous
+        .iter()
+        .filter(|e| !next_by_key.contains_key(&e.key()))
+        .cloned()
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DataTypeInfo;
+
+    fn process(host_name: &str, process_id: i32) -> ProcessInfo {
+        ProcessInfo {
+            registration_clock: 1,
+            host_name: host_name.into(),
+            shm_transport_domain: host_name.into(),
+            process_id,
+            process_name: "sender".into(),
+            unit_name: "sender".into(),
+            process_parameter: String::new(),
+            state_severity: 1,
+            state_severity_level: 1,
+            state_info: String::new(),
+            time_sync_state: 0,
+            time_sync_module_name: String::new(),
+            component_init_state: 0,
+            component_init_info: String::new(),
+            runtime_version: "v6.0.0".into(),
+            config_file_path: String::new(),
+        }
+    }
+
+    fn topic(topic_id: i64, topic_name: &str) -> TopicInfo {
+        TopicInfo {
+            registration_clock: 1,
+            host_name: "host".into(),
+            shm_transport_domain: "host".into(),
+            process_id: 100,
+            process_name: "sender".into(),
+            unit_name: "sender".into(),
+            topic_id,
+            topic_name: topic_name.into(),
+            direction: "publisher".into(),
+            data_type: DataTypeInfo {
+                encoding: "raw".into(),
+                type_name: "bytes".into(),
+                descriptor: vec![],
+            },
+            transport_layers: vec![],
+            topic_size: 8,
+            connections_local: 0,
+            connections_external: 0,
+            message_drops: 0,
+            data_id: 0,
+            data_clock: 0,
+            data_frequency: 0,
+        }
+    }
+
+    fn server(service_id: i64) -> ServerInfo {
+        ServerInfo {
+            registration_clock: 1,
+            host_name: "host".into(),
+            process_name: "server".into(),
+            unit_name: "server".into(),
+            process_id: 200,
+            service_name: "mirror".into(),
+            service_id,
+            version: 1,
+            tcp_port_v0: 0,
+            tcp_port_v1: 4000,
+            methods: vec![MethodInfo {
+                method_name: "echo".into(),
+                request_type: DataTypeInfo {
+                    encoding: String::new(),
+                    type_name: String::new(),
+                    descriptor: vec![],
+                },
+                response_type: DataTypeInfo {
+                    encoding: String::new(),
+                    type_name: String::new(),
+                    descriptor: vec![],
+                },
+                call_count: 0,
+            }],
+        }
+    }
+
+    fn snapshot(processes: Vec<ProcessInfo>, publishers: Vec<TopicInfo>, servers: Vec<ServerInfo>) -> MonitoringSnapshot {
+        MonitoringSnapshot {
+            processes,
+            publishers,
+            subscribers: vec![],
+            servers,
+            clients: vec![],
+        }
+    }
+
+    #[test]
+    fn reports_added_entities() {
+        let previous = snapshot(vec![], vec![topic(1, "a")], vec![]);
+        let next = snapshot(vec![process("host", 100)], vec![topic(1, "a"), topic(2, "b")], vec![]);
+
+        let diff = MonitoringSnapshot::diff(&previous, &next);
+        assert_eq!(diff.publishers.added, vec![topic(2, "b")]);
+        assert_eq!(diff.processes.added, vec![process("host", 100)]);
+        assert!(diff.publishers.removed.is_empty());
+        assert!(diff.publishers.changed.is_empty());
+    }
+
+    #[test]
+    fn reports_removed_entities() {
+        let previous = snapshot(vec![], vec![topic(1, "a"), topic(2, "b")], vec![server(3)]);
+        let next = snapshot(vec![], vec![topic(2, "b")], vec![]);
+
+        let diff = MonitoringSnapshot::diff(&previous, &next);
+        assert_eq!(diff.publishers.removed, vec![topic(1, "a")]);
+        assert_eq!(diff.servers.removed, vec![server(3)]);
+        assert!(diff.publishers.added.is_empty());
+        assert!(diff.publishers.changed.is_empty());
+    }
+
+    #[test]
+    fn reports_changed_entities() {
+        let mut connected = topic(1, "a");
+        connected.connections_local = 1;
+        let previous = snapshot(vec![], vec![topic(1, "a")], vec![]);
+        let next = snapshot(vec![], vec![connected.clone()], vec![]);
+
+        let diff = MonitoringSnapshot::diff(&previous, &next);
+        assert_eq!(
+            diff.publishers.changed,
+            vec![Changed {
+                previous: topic(1, "a"),
+                next: connected,
+            }]
+        );
+        assert!(diff.publishers.added.is_empty());
+        assert!(diff.publishers.removed.is_empty());
+    }
+
+    #[test]
+    fn matches_processes_by_host_and_process_id() {
+        let previous = snapshot(vec![process("host_a", 100)], vec![], vec![]);
+        let next = snapshot(vec![process("host_b", 100)], vec![], vec![]);
+
+        let diff = MonitoringSnapshot::diff(&previous, &next);
+        assert_eq!(diff.processes.added, vec![process("host_b", 100)]);
+        assert_eq!(diff.processes.removed, vec![process("host_a", 100)]);
+    }
+
+    #[test]
+    fn ignores_registration_clock() {
+        let previous = snapshot(vec![process("host", 100)], vec![topic(1, "a")], vec![server(3)]);
+        let mut next = previous.clone();
+        next.processes[0].registration_clock += 1;
+        next.publishers[0].registration_clock += 1;
+        next.servers[0].registration_clock += 1;
+
+        assert!(MonitoringSnapshot::diff(&previous, &next).is_empty());
+    }
+
+    #[test]
+    fn ignores_traffic_counters() {
+        let previous = snapshot(vec![], vec![topic(1, "a")], vec![server(3)]);
+        let mut next = previous.clone();
+        next.publishers[0].data_id += 1;
+        next.publishers[0].data_clock += 10;
+        next.publishers[0].data_frequency = 10_000;
+        next.servers[0].methods[0].call_count += 1;
+
+        assert!(MonitoringSnapshot::diff(&previous, &next).is_empty());
+    }
+}
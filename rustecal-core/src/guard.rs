@@ -0,0 +1,113 @@
+//! Scoped, reference-counted initialization of the eCAL runtime.
+//!
+//! [`EcalGuard`] initializes eCAL when the first guard is created and finalizes
+//! it when the last guard is dropped, including during a panic unwind.
+//!
+//! Several libraries in one process can each hold their own guard without
+//! knowing about each other. Publishers, subscribers, servers and clients
+//! created while a guard is alive keep a clone of it, so eCAL is never
+//! finalized underneath a live handle.
+
+use std::ffi::CString;
+use std::ptr;
+use std::sync::Mutex;
+
+use crate::components::EcalComponents;
+use crate::error::{check, RustecalError};
+
+/// Number of live guards. Guarded by a mutex so that initialization and
+/// finalization never overlap.
+static GUARD_COUNT: Mutex<usize> = Mutex::new(0);
+
+/// Keeps the eCAL runtime alive for as long as it (or any clone) exists.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustecal_core::{EcalComponents, EcalGuard};
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let _ecal = EcalGuard::new(Some("my ecal app"), EcalComponents::DEFAULT)?;
+///
+///     // use publishers, subscribers, clients, server
+///
+///     Ok(())
+/// } // eCAL is finalized here, after all handles have been dropped
+/// ```
+#[derive(Debug)]
+pub struct EcalGuard {
+    _private: (),
+}
+
+impl EcalGuard {
+    /// Initializes eCAL (if no guard is alive yet) and returns a new guard.
+    ///
+    /// If another guard already exists, the running instance is shared and
+    /// `unit_name` and `components` are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(RustecalError::Ecal{..})` on any non‑zero C return code,
+    /// or `RustecalError::Internal` if the unit name contains an interior NUL.
+    pub fn new(
+        unit_name: Option<&str>,
+        components: EcalComponents,
+    ) -> Result<Self, RustecalError> {
+        let mut count = GUARD_COUNT.lock().unwrap_or_else(|e| e.into_inner());
+
+        if *count == 0 {
+            let c_name = unit_name
+                .map(CString::new)
+                .transpose()
+                .map_err(|e| RustecalError::Internal(format!("invalid unit name: {}", e)))?;
+            let name_ptr = c_name.as_ref().map_or(ptr::null(), |c| c.as_ptr());
+
+            let ret = unsafe {
+                rustecal_sys::eCAL_Initialize(name_ptr, &components.bits(), ptr::null())
+            };
+            check(ret)?;
+        }
+
+        *count += 1;
+        Ok(Self { _private: () })
+    }
+
+    /// Returns a new guard sharing the runtime if at least one guard is alive.
+    ///
+    /// This is used by publishers, subscribers and services to tie their
+    /// lifetime to a guarded runtime. It never initializes eCAL.
+    pub fn current() -> Option<Self> {
+        let mut count = GUARD_COUNT.lock().unwrap_or_else(|e| e.into_inner());
+        if *count == 0 {
+            None
+        } else {
+            *count += 1;
+            Some(Self { _private: () })
+        }
+    }
+
+    /// Returns the number of currently alive guards.
+    pub fn count() -> usize {
+        *GUARD_COUNT.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Clone for EcalGuard {
+    fn clone(&self) -> Self {
+        *GUARD_COUNT.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+        Self { _private: () }
+    }
+}
+
+impl Drop for EcalGuard {
+    /// Finalizes eCAL when the last guard goes away.
+    fn drop(&mut self) {
+        let mut count = GUARD_COUNT.lock().unwrap_or_else(|e| e.into_inner());
+        *count -= 1;
+        if *count == 0 {
+            unsafe {
+                rustecal_sys::eCAL_Finalize();
+            }
+        }
+    }
+}
@@ -5,23 +5,34 @@
 //! Includes:
 //! - Initialization (`Ecal::initialize`)
 //! - Finalization (`Ecal::finalize`)
+//! - Scoped, reference-counted initialization (`EcalGuard`)
 //! - System status queries and component management.
+//! - Process state reporting and identity (`Process`).
+//! - Remote shutdown of other eCAL processes (`Util`).
 //!
 //! This crate is typically re-exported by the `rustecal` crate.
 
 pub mod core;
 pub mod components;
 pub mod error;
+pub mod guard;
 pub mod types;
 pub mod log;
 pub mod log_level;
 pub mod core_types;
 pub mod monitoring;
+pub mod process;
+pub mod process_severity;
+pub mod util;
 
 // Re‑exports for ergonomic access:
 pub use core::Ecal;
 pub use components::EcalComponents;
 pub use error::RustecalError;
+pub use guard::EcalGuard;
 pub use log::Log;
 pub use log_level::LogLevel;
+pub use process::Process;
+pub use process_severity::{ProcessSeverity, ProcessSeverityLevel};
+pub use util::Util;
 pub use core_types::logging::LogMessage;
@@ -0,0 +1,74 @@
+//! Process-level information and state reporting.
+//!
+//! This module wraps the C API from `ecal_c/process.h` and lets a Rust process
+//! report its health to eCAL Monitor and query how it is registered in eCAL
+//! (unit name, host name, process id, ...).
+
+use crate::process_severity::{ProcessSeverity, ProcessSeverityLevel};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+/// Provides access to the state and identity of the current eCAL process.
+pub struct Process;
+
+impl Process {
+    /// Sets the process state shown in eCAL Monitor.
+    ///
+    /// Any interior NUL in `info` is replaced with `"<invalid UTF-8>"`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustecal_core::process::Process;
+    /// use rustecal_core::{ProcessSeverity, ProcessSeverityLevel};
+    ///
+    /// Process::set_state(ProcessSeverity::Healthy, ProcessSeverityLevel::Level1, "I feel good");
+    /// ```
+    pub fn set_state(severity: ProcessSeverity, level: ProcessSeverityLevel, info: &str) {
+        let cstr = CString::new(info)
+            .unwrap_or_else(|_| CString::new("<invalid UTF-8>").unwrap());
+
+        unsafe {
+            rustecal_sys::eCAL_Process_SetState(severity.into(), level.into(), cstr.as_ptr());
+        }
+    }
+
+    /// Returns the unit name this process was initialized with.
+    pub fn get_unit_name() -> String {
+        cstr_to_string(unsafe { rustecal_sys::eCAL_Process_GetUnitName() })
+    }
+
+    /// Returns the name of the host this process is running on.
+    pub fn get_host_name() -> String {
+        cstr_to_string(unsafe { rustecal_sys::eCAL_Process_GetHostName() })
+    }
+
+    /// Returns the shared memory transport domain of this process.
+    pub fn get_shm_transport_domain() -> String {
+        cstr_to_string(unsafe { rustecal_sys::eCAL_Process_GetShmTransportDomain() })
+    }
+
+    /// Returns the operating system process id.
+    pub fn get_process_id() -> i32 {
+        unsafe { rustecal_sys::eCAL_Process_GetProcessID() }
+    }
+
+    /// Returns the full process name (path of the executable).
+    pub fn get_process_name() -> String {
+        cstr_to_string(unsafe { rustecal_sys::eCAL_Process_GetProcessName() })
+    }
+
+    /// Returns the command line parameters the process was started with.
+    pub fn get_process_parameter() -> String {
+        cstr_to_string(unsafe { rustecal_sys::eCAL_Process_GetProcessParameter() })
+    }
+}
+
+/// Converts a C string pointer owned by eCAL to a Rust `String`.
+fn cstr_to_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() }
+    }
+}
@@ -0,0 +1,116 @@
+//! eCAL process state severities.
+//!
+//! This mirrors the C enums `eCAL_Process_eSeverity` and
+//! `eCAL_Process_eSeverityLevel` from `ecal_c/process_severity.h`:
+//!
+//! ```c
+//! enum eCAL_Process_eSeverity
+//! {
+//!   eCAL_Process_eSeverity_unknown  = 0,
+//!   eCAL_Process_eSeverity_healthy  = 1,
+//!   eCAL_Process_eSeverity_warning  = 2,
+//!   eCAL_Process_eSeverity_critical = 3,
+//!   eCAL_Process_eSeverity_failed   = 4,
+//! };
+//!
+//! enum eCAL_Process_eSeverityLevel
+//! {
+//!   eCAL_Process_eSeverityLevel_level1 = 1,
+//!   eCAL_Process_eSeverityLevel_level2 = 2,
+//!   eCAL_Process_eSeverityLevel_level3 = 3,
+//!   eCAL_Process_eSeverityLevel_level4 = 4,
+//!   eCAL_Process_eSeverityLevel_level5 = 5,
+//! };
+//! ```
+
+/// Health state of a process as reported to eCAL Monitor.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessSeverity {
+    /// State is not known.
+    Unknown  = 0,
+    /// Process is running as expected.
+    Healthy  = 1,
+    /// Process is running, but something needs attention.
+    Warning  = 2,
+    /// Process is in a critical state.
+    Critical = 3,
+    /// Process has failed.
+    Failed   = 4,
+}
+
+/// Fine-grained level within a [`ProcessSeverity`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessSeverityLevel {
+    /// Level 1 (default).
+    Level1 = 1,
+    /// Level 2.
+    Level2 = 2,
+    /// Level 3.
+    Level3 = 3,
+    /// Level 4.
+    Level4 = 4,
+    /// Level 5.
+    Level5 = 5,
+}
+
+impl From<i32> for ProcessSeverity {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => ProcessSeverity::Healthy,
+            2 => ProcessSeverity::Warning,
+            3 => ProcessSeverity::Critical,
+            4 => ProcessSeverity::Failed,
+            _ => ProcessSeverity::Unknown,
+        }
+    }
+}
+
+impl From<u32> for ProcessSeverity {
+    fn from(value: u32) -> Self {
+        ProcessSeverity::from(value as i32)
+    }
+}
+
+impl From<ProcessSeverity> for i32 {
+    fn from(severity: ProcessSeverity) -> Self {
+        severity as i32
+    }
+}
+
+impl From<ProcessSeverity> for u32 {
+    fn from(severity: ProcessSeverity) -> Self {
+        severity as u32
+    }
+}
+
+impl From<i32> for ProcessSeverityLevel {
+    fn from(value: i32) -> Self {
+        match value {
+            2 => ProcessSeverityLevel::Level2,
+            3 => ProcessSeverityLevel::Level3,
+            4 => ProcessSeverityLevel::Level4,
+            5 => ProcessSeverityLevel::Level5,
+            _ => ProcessSeverityLevel::Level1,
+        }
+    }
+}
+
+impl From<u32> for ProcessSeverityLevel {
+    fn from(value: u32) -> Self {
+        ProcessSeverityLevel::from(value as i32)
+    }
+}
+
+impl From<ProcessSeverityLevel> for i32 {
+    fn from(level: ProcessSeverityLevel) -> Self {
+        level as i32
+    }
+}
+
+impl From<ProcessSeverityLevel> for u32 {
+    fn from(level: ProcessSeverityLevel) -> Self {
+        level as u32
+    }
+}
@@ -1,10 +1,13 @@
 //! Common eCAL types shared across pubsub and service layers.
 
+use std::any::TypeId;
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::os::raw::c_char;
+use std::sync::{OnceLock, RwLock};
 
 /// Represents a globally unique entity in eCAL.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct EntityId {
     pub entity_id: u64,
     pub process_id: i32,
@@ -22,7 +25,7 @@ impl From<rustecal_sys::eCAL_SEntityId> for EntityId {
 }
 
 /// Rust-safe representation of `eCAL_SDataTypeInformation`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DataTypeInfo {
     pub type_name: String,
     pub encoding: String,
@@ -50,6 +53,66 @@ impl From<rustecal_sys::eCAL_SDataTypeInformation> for DataTypeInfo {
     }
 }
 
+/// A message type with datatype metadata, shared by the pub/sub and service layers.
+///
+/// `PublisherMessage` and `SubscriberMessage` build on this trait, so the
+/// metadata of a type is defined once. Services use it to declare typed
+/// methods.
+///
+/// # Example
+///
+/// ```
+/// use rustecal_core::types::{DataTypeInfo, EcalType};
+///
+/// struct Pose;
+///
+/// impl EcalType for Pose {
+///     fn datatype() -> DataTypeInfo {
+///         DataTypeInfo {
+///             encoding: "json".into(),
+///             type_name: "sensors.Pose".into(),
+///             descriptor: vec![],
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Pose::datatype_info().type_name, "sensors.Pose");
+/// ```
+pub trait EcalType: 'static {
+    /// Builds the metadata describing this type (encoding, name, optional descriptor).
+    fn datatype() -> DataTypeInfo;
+
+    /// Returns the metadata of this type, building it on first use only.
+    ///
+    /// Prefer this over [`EcalType::datatype`] on hot paths, as it does not
+    /// allocate after the first call.
+    fn datatype_info() -> &'static DataTypeInfo
+    where
+        Self: Sized,
+    {
+        cached_datatype(TypeId::of::<Self>(), Self::datatype)
+    }
+}
+
+/// Cached metadata per type. Entries are never removed, so they are leaked
+/// once per type to hand out `'static` references.
+static DATATYPES: OnceLock<RwLock<HashMap<TypeId, &'static DataTypeInfo>>> = OnceLock::new();
+
+fn cached_datatype(type_id: TypeId, build: fn() -> DataTypeInfo) -> &'static DataTypeInfo {
+    let cache = DATATYPES.get_or_init(Default::default);
+    if let Some(info) = cache.read().unwrap_or_else(|e| e.into_inner()).get(&type_id) {
+        return info;
+    }
+
+    // Build outside the lock, `build` may query other cached types
+    let info = build();
+    cache
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(type_id)
+        .or_insert_with(|| Box::leak(Box::new(info)))
+}
+
 /// Rust-safe representation of `eCAL_SVersion`.
 #[derive(Debug, Clone)]
 pub struct Version {
@@ -0,0 +1,51 @@
+//! Utility functions to control other eCAL processes.
+//!
+//! This module wraps the shutdown functions from `ecal_c/util.h`, which let a
+//! supervisor request the shutdown of eCAL processes by unit name or process id.
+//!
+//! A process receiving such a request sees [`Ecal::ok`](crate::Ecal::ok) turn
+//! `false` and [`Ecal::shutdown_requested`](crate::Ecal::shutdown_requested)
+//! turn `true`, so the usual `while Ecal::ok() { ... }` loop exits on its own.
+
+use crate::error::RustecalError;
+use std::ffi::CString;
+
+/// Provides functions to request the shutdown of eCAL processes.
+pub struct Util;
+
+impl Util {
+    /// Requests all eCAL processes initialized with the given unit name to shut down.
+    ///
+    /// This matches on the unit name passed to `Ecal::initialize`, not on the
+    /// name of the executable.
+    ///
+    /// Processes are resolved from registration data, so the `MONITORING`
+    /// component should be initialized in the calling process.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RustecalError::Internal` if the unit name contains an interior NUL.
+    pub fn shutdown_unit_by_name(unit_name: &str) -> Result<(), RustecalError> {
+        let c_name = CString::new(unit_name)
+            .map_err(|e| RustecalError::Internal(format!("invalid unit name: {}", e)))?;
+
+        unsafe {
+            rustecal_sys::eCAL_Util_ShutdownUnitName(c_name.as_ptr());
+        }
+        Ok(())
+    }
+
+    /// Requests the eCAL process with the given process id to shut down.
+    pub fn shutdown_process_by_id(process_id: i32) {
+        unsafe {
+            rustecal_sys::eCAL_Util_ShutdownProcessID(process_id);
+        }
+    }
+
+    /// Requests all eCAL processes to shut down.
+    pub fn shutdown_processes() {
+        unsafe {
+            rustecal_sys::eCAL_Util_ShutdownProcesses();
+        }
+    }
+}
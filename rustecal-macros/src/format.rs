@@ -0,0 +1,88 @@
+//! Wire formats supported by the generated code.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, LitStr, Type};
+
+/// A payload encoding and the crate implementing it.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Json,
+    Cbor,
+    Msgpack,
+    Protobuf,
+}
+
+impl Format {
+    pub fn parse(lit: &LitStr) -> syn::Result<Self> {
+        match lit.value().as_str() {
+            "json" => Ok(Format::Json),
+            "cbor" => Ok(Format::Cbor),
+            "msgpack" => Ok(Format::Msgpack),
+            "protobuf" | "proto" => Ok(Format::Protobuf),
+            other => Err(Error::new(
+                lit.span(),
+                format!("unknown format '{}', expected json, cbor, msgpack or protobuf", other),
+            )),
+        }
+    }
+
+    /// The encoding label announced to eCAL.
+    pub fn encoding(self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Cbor => "cbor",
+            Format::Msgpack => "msgpack",
+            Format::Protobuf => "proto",
+        }
+    }
+
+    fn serde_support(self) -> TokenStream {
+        match self {
+            Format::Json => quote!(::rustecal_types_serde::json_message::JsonSupport),
+            Format::Cbor => quote!(::rustecal_types_serde::cbor_message::CborSupport),
+            Format::Msgpack => quote!(::rustecal_types_serde::msgpack_message::MsgpackSupport),
+            Format::Protobuf => unreachable!("protobuf is not a serde format"),
+        }
+    }
+
+    /// Expression encoding `value` (a reference) into a `Vec<u8>`.
+    pub fn encode(self, value: TokenStream) -> TokenStream {
+        match self {
+            Format::Protobuf => quote!(::prost::Message::encode_to_vec(#value)),
+            _ => {
+                let support = self.serde_support();
+                quote!(<#support as ::rustecal_types_serde::format_support::FormatSupport>::encode(#value))
+            }
+        }
+    }
+
+    /// Expression decoding `bytes` (a `&[u8]`) into an `Option<ty>`.
+    pub fn decode(self, bytes: TokenStream, ty: &Type) -> TokenStream {
+        match self {
+            Format::Protobuf => quote!(<#ty as ::prost::Message>::decode(#bytes).ok()),
+            _ => {
+                let support = self.serde_support();
+                quote!(<#support as ::rustecal_types_serde::format_support::FormatSupport>::decode::<#ty>(#bytes))
+            }
+        }
+    }
+
+    /// Expression building the `DataTypeInfo` of `ty`.
+    pub fn datatype(self, ty: &Type, type_name: Option<&str>, descriptor: TokenStream) -> TokenStream {
+        let encoding = self.encoding();
+        let type_name = match type_name {
+            Some(name) => quote!(#name.to_string()),
+            None if self == Format::Protobuf => quote!(<#ty as ::prost::Name>::full_name()),
+            None => quote!(::rustecal_types_serde::format_support::short_type_name::<#ty>()),
+        };
+        quote! {
+            ::rustecal::__private::DataTypeInfo {
+                encoding: #encoding.to_string(),
+                type_name: #type_name,
+                descriptor: #descriptor,
+            }
+        }
+    }
+}
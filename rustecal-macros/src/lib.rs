@@ -0,0 +1,111 @@
+//! # rustecal-macros
+//!
+//! Procedural macros for rustecal. The generated code refers to the
+//! `rustecal` meta crate (feature `macros`) and, depending on the chosen
+//! format, to `rustecal-types-serde` or `prost`.
+//!
+//! ## Macros
+//! - `#[rpc]`: turns a trait into a server registration helper and a typed client.
+//! - `#[derive(EcalMessage)]`: makes a type usable with typed publishers and subscribers.
+
+mod format;
+mod message;
+mod rpc;
+
+use proc_macro::TokenStream;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, DeriveInput, ItemTrait, Meta, Token};
+
+/// Generates a typed eCAL service from a trait.
+///
+/// For a trait `Mirror`, two types are generated next to it:
+///
+/// - `MirrorServer::register(&server, service)` adds one method per trait
+///   function to a `ServiceServer`, decoding requests and encoding responses.
+/// - `MirrorClient::new(service_name)` is a client with one method per trait
+///   function, returning `Result<Response, CallError>`.
+///
+/// Arguments and return values must be owned types. Methods with several
+/// arguments send them as a tuple. A method may return `Result<T, E>` with
+/// `E: Into<ServiceError>`; the error is reported to the client as a failed call.
+///
+/// The `format` argument selects the encoding: `json` (default), `cbor`,
+/// `msgpack` (via `rustecal-types-serde`) or `protobuf` (via `prost`, exactly
+/// one message argument per method, types implementing `prost::Name`).
+///
+/// # Example
+///
+/// ```ignore
+/// use rustecal::rpc;
+///
+/// #[rpc(format = "json")]
+/// pub trait Mirror {
+///     fn echo(&self, text: String) -> String;
+///     fn reverse(&self, text: String) -> String;
+/// }
+///
+/// struct MirrorImpl;
+///
+/// impl Mirror for MirrorImpl {
+///     fn echo(&self, text: String) -> String { text }
+///     fn reverse(&self, text: String) -> String { text.chars().rev().collect() }
+/// }
+///
+/// let server = rustecal::ServiceServer::new("mirror")?;
+/// MirrorServer::register(&server, MirrorImpl)?;
+///
+/// let client = MirrorClient::new("mirror")?;
+/// let reversed = client.reverse("stressed".into())?;
+/// ```
+#[proc_macro_attribute]
+pub fn rpc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr with Punctuated::<Meta, Token![,]>::parse_terminated);
+    let item = parse_macro_input!(item as ItemTrait);
+
+    rpc::RpcArgs::parse(args)
+        .and_then(|args| rpc::expand(args, item))
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Implements `EcalType`, `PublisherMessage` and `SubscriberMessage` for a type.
+///
+/// The type is encoded directly, without a wrapper such as `JsonMessage`.
+/// Options are given in an `#[ecal(...)]` attribute:
+///
+/// - `format`: `json` (default), `cbor`, `msgpack` (the type implements
+///   `serde::Serialize` and `Deserialize`) or `protobuf` (the type implements
+///   `prost::Message` and, without `type_name`, `prost::Name`).
+/// - `type_name`: the type name announced to eCAL, e.g. to match the names
+///   used by C++ applications. Defaults to the Rust type name, or to the full
+///   protobuf name such as `pkg.Foo` for `protobuf`.
+/// - `descriptor`: a file embedded as descriptor, relative to the crate root.
+/// - `descriptor_fn`: a function `fn() -> Vec<u8>` returning the descriptor,
+///   e.g. one that reads a descriptor set generated into `OUT_DIR`.
+///
+/// Generic types are not supported.
+///
+/// # Example
+///
+/// ```ignore
+/// use rustecal::{EcalMessage, TypedPublisher};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, EcalMessage)]
+/// #[ecal(format = "json", type_name = "sensors.Pose")]
+/// struct Pose {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let publisher = TypedPublisher::<Pose>::new("pose")?;
+/// publisher.send(&Pose { x: 1.0, y: 2.0 });
+/// ```
+#[proc_macro_derive(EcalMessage, attributes(ecal))]
+pub fn derive_ecal_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    message::expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
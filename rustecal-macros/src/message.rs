@@ -0,0 +1,116 @@
+//! Expansion of `#[derive(EcalMessage)]`: a type becomes publishable and subscribable.
+
+use crate::format::Format;
+use crate::rpc::string_literal;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{DeriveInput, Error, LitStr, Meta, Path, Token};
+
+/// Where the descriptor announced with the type comes from.
+enum Descriptor {
+    None,
+    /// A file, relative to the crate root, embedded with `include_bytes!`.
+    File(LitStr),
+    /// A function returning the descriptor bytes.
+    Function(Path),
+}
+
+/// Options given in `#[ecal(...)]`.
+struct MessageArgs {
+    format: Format,
+    type_name: Option<LitStr>,
+    descriptor: Descriptor,
+}
+
+impl MessageArgs {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut result = MessageArgs {
+            format: Format::default(),
+            type_name: None,
+            descriptor: Descriptor::None,
+        };
+
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("ecal")) {
+            let args = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            for arg in args {
+                let Meta::NameValue(nv) = &arg else {
+                    return Err(Error::new(arg.span(), "expected `name = \"...\"`"));
+                };
+                let value = string_literal(&nv.value)?;
+
+                if nv.path.is_ident("format") {
+                    result.format = Format::parse(&value)?;
+                } else if nv.path.is_ident("type_name") {
+                    result.type_name = Some(value);
+                } else if nv.path.is_ident("descriptor") || nv.path.is_ident("descriptor_fn") {
+                    if !matches!(result.descriptor, Descriptor::None) {
+                        return Err(Error::new(nv.span(), "only one descriptor source is allowed"));
+                    }
+                    result.descriptor = if nv.path.is_ident("descriptor") {
+                        Descriptor::File(value)
+                    } else {
+                        Descriptor::Function(value.parse()?)
+                    };
+                } else {
+                    return Err(Error::new(
+                        nv.path.span(),
+                        "unknown argument, expected `format`, `type_name`, `descriptor` or `descriptor_fn`",
+                    ));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "EcalMessage cannot be derived for generic types",
+        ));
+    }
+
+    let args = MessageArgs::parse(&input)?;
+    let format = args.format;
+    let ident = &input.ident;
+    let ty: syn::Type = syn::parse_quote!(#ident);
+
+    let descriptor = match &args.descriptor {
+        Descriptor::None => quote!(::std::vec::Vec::new()),
+        Descriptor::File(path) => quote! {
+            ::std::include_bytes!(::std::concat!(::std::env!("CARGO_MANIFEST_DIR"), "/", #path)).to_vec()
+        },
+        Descriptor::Function(path) => quote!(#path()),
+    };
+    let type_name = args.type_name.as_ref().map(LitStr::value);
+    let datatype = format.datatype(&ty, type_name.as_deref(), descriptor);
+    let encode = format.encode(quote!(self));
+    let decode = format.decode(quote!(&bytes[..]), &ty);
+
+    Ok(quote! {
+        impl ::rustecal::__private::EcalType for #ident {
+            fn datatype() -> ::rustecal::__private::DataTypeInfo {
+                #datatype
+            }
+        }
+
+        impl ::rustecal::__private::PublisherMessage for #ident {
+            fn to_bytes(&self) -> ::std::sync::Arc<[u8]> {
+                ::std::sync::Arc::from(#encode)
+            }
+        }
+
+        impl ::rustecal::__private::SubscriberMessage for #ident {
+            fn from_bytes(
+                bytes: ::std::sync::Arc<[u8]>,
+                _data_type_info: &::rustecal::__private::DataTypeInfo,
+            ) -> ::std::option::Option<Self> {
+                #decode
+            }
+        }
+    })
+}
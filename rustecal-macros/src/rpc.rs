@@ -0,0 +1,300 @@
+//! Expansion of `#[rpc]`: a trait becomes a server registration helper and a typed client.
+
+use crate::format::Format;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Error, Expr, ExprLit, FnArg, GenericArgument, Ident, ItemTrait, Lit, LitStr, Meta,
+    PathArguments, ReturnType, Token, TraitItem, Type,
+};
+
+/// Options given to the attribute, e.g. `#[rpc(format = "json")]`.
+#[derive(Default)]
+pub struct RpcArgs {
+    format: Format,
+}
+
+impl RpcArgs {
+    pub fn parse(args: Punctuated<Meta, Token![,]>) -> syn::Result<Self> {
+        let mut result = RpcArgs::default();
+        for arg in args {
+            let Meta::NameValue(nv) = &arg else {
+                return Err(Error::new(arg.span(), "expected `format = \"...\"`"));
+            };
+            if nv.path.is_ident("format") {
+                result.format = Format::parse(&string_literal(&nv.value)?)?;
+            } else {
+                return Err(Error::new(nv.path.span(), "unknown argument, expected `format`"));
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Returns the string literal `expr` consists of.
+pub fn string_literal(expr: &Expr) -> syn::Result<LitStr> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Str(s), .. }) => Ok(s.clone()),
+        other => Err(Error::new(other.span(), "expected a string literal")),
+    }
+}
+
+/// One RPC method of the trait.
+struct Method {
+    ident: Ident,
+    name: String,
+    arg_types: Vec<Type>,
+    /// The success type returned to the client.
+    response: Type,
+    /// Whether the trait method returns `Result<_, E>`.
+    fallible: bool,
+}
+
+impl Method {
+    fn parse(item: &syn::TraitItemFn, format: Format) -> syn::Result<Self> {
+        let sig = &item.sig;
+        if sig.asyncness.is_some() || !sig.generics.params.is_empty() {
+            return Err(Error::new(sig.span(), "rpc methods must not be async or generic"));
+        }
+
+        let mut inputs = sig.inputs.iter();
+        match inputs.next() {
+            Some(FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_none() => {}
+            _ => return Err(Error::new(sig.span(), "rpc methods must take `&self`")),
+        }
+
+        let arg_types: Vec<Type> = inputs
+            .map(|arg| match arg {
+                FnArg::Typed(pat) => Ok((*pat.ty).clone()),
+                FnArg::Receiver(r) => Err(Error::new(r.span(), "unexpected receiver")),
+            })
+            .collect::<syn::Result<_>>()?;
+
+        if format == Format::Protobuf && arg_types.len() != 1 {
+            return Err(Error::new(
+                sig.span(),
+                "protobuf rpc methods take exactly one message argument",
+            ));
+        }
+
+        let (response, fallible) = match &sig.output {
+            ReturnType::Default => (syn::parse_quote!(()), false),
+            ReturnType::Type(_, ty) => match result_ok_type(ty) {
+                Some(ok) => (ok, true),
+                None => ((**ty).clone(), false),
+            },
+        };
+
+        Ok(Method {
+            ident: sig.ident.clone(),
+            name: sig.ident.to_string(),
+            arg_types,
+            response,
+            fallible,
+        })
+    }
+
+    /// The type the arguments are encoded as: `()`, the single argument, or a tuple.
+    fn request_type(&self) -> Type {
+        match self.arg_types.as_slice() {
+            [single] => single.clone(),
+            types => syn::parse_quote!((#(#types),*)),
+        }
+    }
+
+    fn arg_idents(&self) -> Vec<Ident> {
+        (0..self.arg_types.len()).map(|i| format_ident!("arg{}", i)).collect()
+    }
+
+    /// Expression bundling the argument idents into the request value.
+    fn request_value(&self) -> TokenStream {
+        match self.arg_idents().as_slice() {
+            [single] => quote!(#single),
+            idents => quote!((#(#idents),*)),
+        }
+    }
+}
+
+/// Returns `T` if `ty` is `Result<T, E>`.
+fn result_ok_type(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else { return None };
+    let last = path.path.segments.last()?;
+    if last.ident != "Result" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &last.arguments else { return None };
+    if args.args.len() != 2 {
+        return None;
+    }
+    match args.args.first()? {
+        GenericArgument::Type(ok) => Some(ok.clone()),
+        _ => None,
+    }
+}
+
+pub fn expand(args: RpcArgs, item: ItemTrait) -> syn::Result<TokenStream> {
+    let format = args.format;
+    let vis = &item.vis;
+    let trait_ident = &item.ident;
+    let server_ident = format_ident!("{}Server", trait_ident);
+    let client_ident = format_ident!("{}Client", trait_ident);
+
+    let methods: Vec<Method> = item
+        .items
+        .iter()
+        .filter_map(|i| match i {
+            TraitItem::Fn(f) => Some(Method::parse(f, format)),
+            _ => None,
+        })
+        .collect::<syn::Result<_>>()?;
+
+    let registrations = methods.iter().map(|m| {
+        let name = &m.name;
+        let ident = &m.ident;
+        let idents = m.arg_idents();
+        let request_type = m.request_type();
+        let decode_error = format!("failed to decode request of method '{}'", name);
+
+        let decode = if m.arg_types.is_empty() {
+            quote!()
+        } else {
+            let value = m.request_value();
+            let decode = format.decode(quote!(request), &request_type);
+            quote! {
+                let #value: #request_type = #decode
+                    .ok_or_else(|| ::rustecal::__private::ServiceError::new(#decode_error))?;
+            }
+        };
+
+        let call = if m.fallible {
+            quote!(service.#ident(#(#idents),*).map_err(::std::convert::Into::<::rustecal::__private::ServiceError>::into)?)
+        } else {
+            quote!(service.#ident(#(#idents),*))
+        };
+        let encode = format.encode(quote!(&response));
+        // announced like the client declares them, so monitoring shows the types
+        let request_datatype = format.datatype(&request_type, None, quote!(::std::vec::Vec::new()));
+        let response_datatype = format.datatype(&m.response, None, quote!(::std::vec::Vec::new()));
+
+        quote! {
+            {
+                let service = service.clone();
+                server.add_typed_method(
+                    #name,
+                    &#request_datatype,
+                    &#response_datatype,
+                    ::std::boxed::Box::new(move |_info, request: &[u8]| {
+                        #decode
+                        let response = #call;
+                        Ok(#encode)
+                    }),
+                )?;
+            }
+        }
+    });
+
+    let declarations = methods.iter().map(|m| {
+        let name = &m.name;
+        let request = format.datatype(&m.request_type(), None, quote!(::std::vec::Vec::new()));
+        let response = format.datatype(&m.response, None, quote!(::std::vec::Vec::new()));
+        quote!(::rustecal::__private::ClientMethod::with_types(#name, #request, #response))
+    });
+
+    let client_methods = methods.iter().map(|m| {
+        let name = &m.name;
+        let ident = &m.ident;
+        let idents = m.arg_idents();
+        let types = &m.arg_types;
+        let response = &m.response;
+        let encode = format.encode({
+            let value = m.request_value();
+            quote!(&#value)
+        });
+        let decode = format.decode(quote!(&success.payload), response);
+        let doc = format!("Calls `{}` on the first server that answers successfully.", name);
+        let decode_error = format!("failed to decode response of method '{}'", name);
+
+        quote! {
+            #[doc = #doc]
+            #vis fn #ident(&self, #(#idents: #types),*) -> ::std::result::Result<#response, ::rustecal::__private::CallError> {
+                let request = ::rustecal::__private::ServiceRequest { payload: #encode };
+                let responses = self.client.call(#name, request, self.timeout_ms)?;
+                match responses.iter().find(|r| r.is_success()) {
+                    Some(success) => #decode
+                        .ok_or_else(|| ::rustecal::__private::CallError::Failed(#decode_error.into())),
+                    None => Err(::rustecal::__private::CallError::Failed(
+                        responses
+                            .into_iter()
+                            .find_map(|r| r.error_msg)
+                            .unwrap_or_else(|| "call failed".into()),
+                    )),
+                }
+            }
+        }
+    });
+
+    let server_doc = format!(
+        "Registers the methods of [`{}`] on a `ServiceServer`.",
+        trait_ident
+    );
+    let client_doc = format!(
+        "Typed client for services implementing [`{}`].",
+        trait_ident
+    );
+
+    Ok(quote! {
+        #item
+
+        #[doc = #server_doc]
+        #vis struct #server_ident;
+
+        impl #server_ident {
+            /// Adds one method per trait function to `server`, dispatching to `service`.
+            #vis fn register<S>(
+                server: &::rustecal::__private::ServiceServer,
+                service: S,
+            ) -> ::std::result::Result<(), ::std::string::String>
+            where
+                S: #trait_ident + ::std::marker::Send + ::std::marker::Sync + 'static,
+            {
+                let service = ::std::sync::Arc::new(service);
+                #(#registrations)*
+                Ok(())
+            }
+        }
+
+        #[doc = #client_doc]
+        #vis struct #client_ident {
+            client: ::rustecal::__private::ServiceClient,
+            timeout_ms: ::std::option::Option<i32>,
+        }
+
+        impl #client_ident {
+            /// Creates a client for `service_name`, declaring all methods with their types.
+            ///
+            /// Calls time out after 1000 ms unless changed with `with_timeout`.
+            #vis fn new(service_name: &str) -> ::std::result::Result<Self, ::std::string::String> {
+                let client = ::rustecal::__private::ServiceClient::with_methods(
+                    service_name,
+                    ::std::vec![#(#declarations),*],
+                )?;
+                Ok(Self { client, timeout_ms: Some(1000) })
+            }
+
+            /// Sets the call timeout in milliseconds; `None` waits indefinitely.
+            #vis fn with_timeout(mut self, timeout_ms: ::std::option::Option<i32>) -> Self {
+                self.timeout_ms = timeout_ms;
+                self
+            }
+
+            /// Returns the underlying untyped client.
+            #vis fn client(&self) -> &::rustecal::__private::ServiceClient {
+                &self.client
+            }
+
+            #(#client_methods)*
+        }
+    })
+}
@@ -0,0 +1,22 @@
+//! # rustecal-play
+//!
+//! Replays recorded MCAP files onto eCAL topics.
+//!
+//! ## Features
+//! - Every recorded topic is republished with its original `DataTypeInfo`.
+//! - Original timing, scaled by a speed factor.
+//! - Looping, topic remapping and start/stop offsets.
+//! - Reads files of `rustecal-record` and other MCAP writers.
+//!
+//! ## Key Types
+//! - `Recording`: the channels and messages of one or more MCAP files
+//! - `Player`: publishes a `Recording`
+//! - `PlayOptions`: speed, looping, range and remapping
+//!
+//! The crate also builds the `rustecal-play` command line player.
+
+pub mod player;
+pub mod recording;
+
+pub use player::{PlayOptions, Player};
+pub use recording::{RecordedChannel, RecordedMessage, Recording};
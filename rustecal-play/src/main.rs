@@ -0,0 +1,94 @@
+//! `rustecal-play`: replays MCAP files onto eCAL topics.
+
+use clap::Parser;
+use rustecal_core::{Ecal, EcalComponents};
+use rustecal_play::{PlayOptions, Player, Recording};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Replay recorded MCAP files onto eCAL topics.
+#[derive(Parser)]
+#[command(name = "rustecal-play", version)]
+struct Args {
+    /// MCAP files to play, e.g. all rotated files of one recording
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Playback speed relative to the original timing
+    #[arg(short, long, default_value_t = 1.0)]
+    speed: f64,
+
+    /// Start over once the end is reached, until Ctrl+C
+    #[arg(short, long = "loop")]
+    looping: bool,
+
+    /// Skip the first SECONDS of the recording
+    #[arg(long, value_name = "SECONDS")]
+    start: Option<f64>,
+
+    /// Stop SECONDS after the beginning of the recording
+    #[arg(long, value_name = "SECONDS")]
+    stop: Option<f64>,
+
+    /// Publish the recorded topic FROM on TO
+    #[arg(short, long, value_name = "FROM=TO", value_parser = parse_remap)]
+    remap: Vec<(String, String)>,
+}
+
+fn parse_remap(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((from, to)) if !from.is_empty() && !to.is_empty() => Ok((from.to_string(), to.to_string())),
+        _ => Err(format!("expected FROM=TO, got '{}'", arg)),
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let recording = Recording::open_all(&args.files)?;
+    let options = PlayOptions {
+        speed: args.speed,
+        looping: args.looping,
+        start: args.start.map(Duration::try_from_secs_f64).transpose()?,
+        stop: args.stop.map(Duration::try_from_secs_f64).transpose()?,
+        remap: args.remap.into_iter().collect(),
+    };
+
+    Ecal::initialize(Some("rustecal play"), EcalComponents::DEFAULT)?;
+
+    let player = match Player::new(recording, options) {
+        Ok(player) => Arc::new(player),
+        Err(e) => {
+            Ecal::finalize();
+            return Err(e.into());
+        }
+    };
+    {
+        let player = Arc::downgrade(&player);
+        ctrlc::set_handler(move || {
+            if let Some(player) = player.upgrade() {
+                player.stop();
+            }
+        })?;
+    }
+
+    let counts = player.recording().message_counts();
+    for (index, channel) in player.recording().channels().iter().enumerate() {
+        println!(
+            "{:<30} {:>8} messages  {} ({})",
+            player.topic_name(index).unwrap_or_default(),
+            counts[index],
+            channel.datatype.type_name,
+            channel.datatype.encoding,
+        );
+    }
+    println!("Playing {:.1} s at {}x...", player.recording().duration().as_secs_f64(), args.speed);
+
+    let published = player.play();
+    println!("Published {} messages", published);
+
+    drop(player);
+    Ecal::finalize();
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Republishing a recording onto eCAL topics.
+
+use crate::recording::{RecordedMessage, Recording};
+use rustecal_pubsub::Publisher;
+use std::collections::HashMap;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+/// Options for [`Player`].
+#[derive(Debug, Clone)]
+pub struct PlayOptions {
+    /// Playback speed relative to the original timing, e.g. `2.0` plays
+    /// twice as fast. Must be greater than zero.
+    pub speed: f64,
+    /// Start over once the end is reached, until stopped. Every round takes
+    /// at least [`Player::MIN_LOOP_PERIOD`].
+    pub looping: bool,
+    /// Skip the messages logged before this offset from the first message.
+    pub start: Option<Duration>,
+    /// Skip the messages logged after this offset from the first message.
+    pub stop: Option<Duration>,
+    /// Recorded topic name -> topic name to publish on.
+    pub remap: HashMap<String, String>,
+}
+
+impl Default for PlayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            looping: false,
+            start: None,
+            stop: None,
+            remap: HashMap::new(),
+        }
+    }
+}
+
+impl PlayOptions {
+    /// Sets the playback speed.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Enables or disables looping.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Plays only the messages between `start` and `stop`, as offsets from
+    /// the first message.
+    pub fn with_range(mut self, start: Option<Duration>, stop: Option<Duration>) -> Self {
+        self.start = start;
+        self.stop = stop;
+        self
+    }
+
+    /// Publishes the recorded topic `from` on `to`.
+    pub fn with_remap(mut self, from: &str, to: &str) -> Self {
+        self.remap.insert(from.to_string(), to.to_string());
+        self
+    }
+}
+
+/// Publishes the messages of a [`Recording`] with their original timing.
+///
+/// Every recorded channel gets a `Publisher` with the recorded
+/// `DataTypeInfo`. [`Player::play`] blocks until playback ends;
+/// [`Player::stop`] ends it early from another thread.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustecal_play::{PlayOptions, Player, Recording};
+///
+/// let recording = Recording::open("recording.mcap").unwrap();
+/// let player = Player::new(recording, PlayOptions::default().with_speed(2.0)).unwrap();
+/// let published = player.play();
+/// ```
+pub struct Player {
+    recording: Recording,
+    options: PlayOptions,
+    /// One publisher per channel, indexed like `Recording::channels`.
+    publishers: Vec<Publisher>,
+    stop: Mutex<bool>,
+    wake: Condvar,
+}
+
+impl Player {
+    /// Minimum duration of one round when looping, so that a selection whose
+    /// messages share a single log time is not republished in a busy loop.
+    pub const MIN_LOOP_PERIOD: Duration = Duration::from_millis(10);
+
+    /// Creates the publishers for all channels of `recording`.
+    pub fn new(recording: Recording, options: PlayOptions) -> Result<Self, String> {
+        if !(options.speed > 0.0 && options.speed.is_finite()) {
+            return Err(format!("Invalid playback speed {}", options.speed));
+        }
+
+        let publishers = recording
+            .channels()
+            .iter()
+            .map(|channel| {
+                let topic_name = options.remap.get(&channel.topic_name).unwrap_or(&channel.topic_name);
+                Publisher::new(topic_name, channel.datatype.clone())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            recording,
+            options,
+            publishers,
+            stop: Mutex::new(false),
+            wake: Condvar::new(),
+        })
+    }
+
+    /// Returns the recording played.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Returns the topic name the channel with `index` is published on.
+    pub fn topic_name(&self, index: usize) -> Option<&str> {
+        let channel = self.recording.channels().get(index)?;
+        Some(self.options.remap.get(&channel.topic_name).unwrap_or(&channel.topic_name))
+    }
+
+    /// Publishes the selected messages, waiting between them as recorded.
+    ///
+    /// Returns the number of messages published once the end is reached
+    /// (never, when looping) or [`Player::stop`] is called.
+    pub fn play(&self) -> u64 {
+        let messages = self.selected();
+        let Some(first) = messages.first() else {
+            return 0;
+        };
+
+        let mut published = 0;
+        loop {
+            let started = Instant::now();
+            for message in messages {
+                let offset = Duration::from_nanos(message.log_time - first.log_time).div_f64(self.options.speed);
+                if !self.wait_until(started + offset) {
+                    return published;
+                }
+                self.publishers[message.channel].send(&message.data);
+                published += 1;
+            }
+
+            if !self.options.looping || !self.wait_until(started + Self::MIN_LOOP_PERIOD) {
+                return published;
+            }
+        }
+    }
+
+    /// Ends a running [`Player::play`]; later calls to `play` return immediately.
+    pub fn stop(&self) {
+        *self.stop.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        self.wake.notify_all();
+    }
+
+    /// The messages between the start and stop offsets.
+    fn selected(&self) -> &[RecordedMessage] {
+        let messages = self.recording.messages();
+        let Some(start_time) = self.recording.start_time() else {
+            return messages;
+        };
+        let offset = |m: &RecordedMessage| Duration::from_nanos(m.log_time - start_time);
+
+        let begin = match self.options.start {
+            Some(start) => messages.partition_point(|m| offset(m) < start),
+            None => 0,
+        };
+        let end = match self.options.stop {
+            Some(stop) => messages.partition_point(|m| offset(m) <= stop),
+            None => messages.len(),
+        };
+        &messages[begin..end.max(begin)]
+    }
+
+    /// Waits until `deadline`; returns `false` if stopped before.
+    fn wait_until(&self, deadline: Instant) -> bool {
+        let stop = self.stop.lock().unwrap_or_else(|e| e.into_inner());
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let (stop, _) = self
+            .wake
+            .wait_timeout_while(stop, timeout, |stop| !*stop && Instant::now() < deadline)
+            .unwrap_or_else(|e| e.into_inner());
+        !*stop
+    }
+}
@@ -0,0 +1,160 @@
+//! Reading recorded MCAP files.
+//!
+//! The `DataTypeInfo` of every channel is restored from the channel metadata
+//! written by `rustecal-record` (`ecal.encoding`, `ecal.type_name`) and the
+//! channel's schema. Files written by other tools fall back to the MCAP
+//! message encoding and the schema name.
+
+use memmap2::Mmap;
+use rustecal_core::types::DataTypeInfo;
+use rustecal_record::writer::ecal_encoding;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub use rustecal_record::writer::{ENCODING_KEY, TYPE_NAME_KEY};
+
+/// A recorded topic.
+#[derive(Debug, Clone)]
+pub struct RecordedChannel {
+    pub topic_name: String,
+    pub datatype: DataTypeInfo,
+}
+
+/// A recorded message.
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    /// Index into [`Recording::channels`].
+    pub channel: usize,
+    /// Receive time in nanoseconds since the Unix epoch.
+    pub log_time: u64,
+    /// Send time in nanoseconds since the Unix epoch.
+    pub publish_time: u64,
+    /// The publisher's message counter.
+    pub sequence: u32,
+    pub data: Vec<u8>,
+}
+
+/// The channels and messages of one or more MCAP files, ordered by log time.
+///
+/// Files are memory-mapped while reading; the messages are kept in memory.
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    channels: Vec<RecordedChannel>,
+    messages: Vec<RecordedMessage>,
+}
+
+impl Recording {
+    /// Reads a single MCAP file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::open_all(&[path.as_ref().to_path_buf()])
+    }
+
+    /// Reads several MCAP files, e.g. the rotated files of one recording,
+    /// into a single recording. Channels with equal topic and datatype are merged.
+    pub fn open_all(paths: &[PathBuf]) -> Result<Self, String> {
+        let mut recording = Self::default();
+        for path in paths {
+            recording.read(path)?;
+        }
+        // stable, so messages with equal log time keep their file order
+        recording.messages.sort_by_key(|m| m.log_time);
+        Ok(recording)
+    }
+
+    fn read(&mut self, path: &Path) -> Result<(), String> {
+        let file = File::open(path).map_err(|e| format!("Failed to open '{}': {}", path.display(), e))?;
+        // SAFETY: the mapping is only read, and dropped before this function
+        // returns; a file truncated meanwhile by another process is not supported.
+        let mapped = unsafe { Mmap::map(&file) }.map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+        let stream = mcap::MessageStream::new(&mapped).map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+
+        // MCAP channel id of this file -> index into self.channels
+        let mut channel_index: HashMap<u16, usize> = HashMap::new();
+        for message in stream {
+            let message = message.map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+
+            let channel = match channel_index.get(&message.channel.id) {
+                Some(&index) => index,
+                None => {
+                    let index = self.channel(&message.channel);
+                    channel_index.insert(message.channel.id, index);
+                    index
+                }
+            };
+
+            self.messages.push(RecordedMessage {
+                channel,
+                log_time: message.log_time,
+                publish_time: message.publish_time,
+                sequence: message.sequence,
+                data: message.data.into_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the index of the channel matching `channel`, adding it if new.
+    fn channel(&mut self, channel: &mcap::Channel<'_>) -> usize {
+        let schema = channel.schema.as_deref();
+        let datatype = DataTypeInfo {
+            encoding: match channel.metadata.get(ENCODING_KEY) {
+                Some(encoding) => encoding.clone(),
+                None => ecal_encoding(&channel.message_encoding).to_string(),
+            },
+            type_name: match (channel.metadata.get(TYPE_NAME_KEY), schema) {
+                (Some(type_name), _) => type_name.clone(),
+                (None, Some(schema)) => schema.name.clone(),
+                (None, None) => String::new(),
+            },
+            descriptor: schema.map(|s| s.data.to_vec()).unwrap_or_default(),
+        };
+
+        let known = self.channels.iter().position(|c| {
+            c.topic_name == channel.topic
+                && c.datatype.encoding == datatype.encoding
+                && c.datatype.type_name == datatype.type_name
+        });
+        known.unwrap_or_else(|| {
+            self.channels.push(RecordedChannel {
+                topic_name: channel.topic.clone(),
+                datatype,
+            });
+            self.channels.len() - 1
+        })
+    }
+
+    /// Returns all recorded topics.
+    pub fn channels(&self) -> &[RecordedChannel] {
+        &self.channels
+    }
+
+    /// Returns all messages, ordered by log time.
+    pub fn messages(&self) -> &[RecordedMessage] {
+        &self.messages
+    }
+
+    /// Returns the log time of the first message, if any.
+    pub fn start_time(&self) -> Option<u64> {
+        self.messages.first().map(|m| m.log_time)
+    }
+
+    /// Returns the time between the first and the last message.
+    pub fn duration(&self) -> Duration {
+        match (self.messages.first(), self.messages.last()) {
+            (Some(first), Some(last)) => Duration::from_nanos(last.log_time - first.log_time),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Returns the number of messages recorded on each channel, indexed like
+    /// [`Recording::channels`].
+    pub fn message_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.channels.len()];
+        for message in &self.messages {
+            counts[message.channel] += 1;
+        }
+        counts
+    }
+}
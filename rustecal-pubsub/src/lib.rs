@@ -11,10 +11,22 @@
 //! - `TypedPublisher<T>`
 //! - `TypedSubscriber<T>`
 //! - Supported types: `StringMessage`, `BytesMessage`, `ProtobufMessage<T>`
+//!
+//! ## Thread Safety
+//! `Publisher`, `Subscriber` and their typed wrappers are `Send + Sync`.
+//! The underlying eCAL handles synchronize sending and callback registration
+//! internally, so a publisher can be moved into a worker thread or shared
+//! between threads through an `Arc`.
+//!
+//! Receive callbacks run on eCAL's receive threads and must therefore be
+//! `Send + Sync`. They can be replaced or removed at any time, also while
+//! messages are being received: a replaced closure is only dropped once a
+//! running invocation has returned.
 
 
 // Re-export core init & types
-pub use rustecal_core::{Ecal, EcalComponents};
+pub use rustecal_core::{Ecal, EcalComponents, EcalGuard};
+pub use rustecal_core::types::EcalType;
 
 // Sub‑modules
 pub mod types;
@@ -22,11 +34,13 @@ pub mod publisher;
 pub mod subscriber;
 pub mod typed_publisher;
 pub mod typed_subscriber;
+pub mod topic_watcher;
 
 // Public API
 pub use publisher::Publisher;
-pub use subscriber::Subscriber;
+pub use subscriber::{RawCallback, RawReceived, Subscriber};
 pub use typed_publisher::TypedPublisher;
 pub use typed_publisher::PublisherMessage;
 pub use typed_subscriber::TypedSubscriber;
 pub use typed_subscriber::SubscriberMessage;
+pub use topic_watcher::{TopicPattern, TopicWatcher};
@@ -1,5 +1,6 @@
 use rustecal_sys::*;
 use rustecal_core::types::DataTypeInfo;
+use rustecal_core::EcalGuard;
 use crate::types::TopicId;
 use std::ffi::{CStr, CString};
 use std::ptr;
@@ -9,11 +10,17 @@ use std::ptr;
 /// This struct provides a high-level interface for sending serialized messages to
 /// a topic using eCAL. It manages the lifecycle of the underlying eCAL publisher handle
 /// and exposes convenient methods to access metadata and send data.
+///
+/// # Thread Safety
+///
+/// `Publisher` is `Send + Sync`, see the [crate documentation](crate#thread-safety).
 pub struct Publisher {
     handle: *mut eCAL_Publisher,
     _encoding: CString,
     _type_name: CString,
     _descriptor: Vec<u8>,
+    /// Keeps a guarded eCAL runtime alive while this handle exists.
+    _guard: Option<EcalGuard>,
 }
 
 impl Publisher {
@@ -57,6 +64,7 @@ impl Publisher {
                 _encoding: c_encoding,
                 _type_name: c_type_name,
                 _descriptor: data_type.descriptor,
+                _guard: EcalGuard::current(),
             })
         }
     }
@@ -181,6 +189,11 @@ impl Publisher {
     }
 }
 
+// SAFETY: the eCAL C publisher is internally synchronized, all other fields
+// are owned and only read after construction.
+unsafe impl Send for Publisher {}
+unsafe impl Sync for Publisher {}
+
 impl Drop for Publisher {
     /// Cleans up the underlying eCAL publisher resource.
     fn drop(&mut self) {
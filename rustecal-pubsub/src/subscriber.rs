@@ -1,20 +1,74 @@
 use rustecal_sys::*;
 use rustecal_core::types::DataTypeInfo;
+use rustecal_core::EcalGuard;
 use crate::types::TopicId;
 use std::ffi::{CStr, CString};
 use std::ptr;
 use std::ffi::c_void;
+use std::slice;
+use std::sync::RwLock;
+
+/// A message received by a [`Subscriber`] closure, before any decoding.
+///
+/// The payload is borrowed from eCAL's receive buffer and only valid during
+/// the callback; copy it with `to_vec()` to keep it.
+#[derive(Debug, Clone)]
+pub struct RawReceived<'a> {
+    /// The serialized payload.
+    pub bytes: &'a [u8],
+    /// The topic and publisher the message was received from.
+    pub topic_id: TopicId,
+    /// The datatype announced by the publisher.
+    pub datatype: DataTypeInfo,
+    /// The send timestamp provided by the publisher (microseconds since epoch).
+    pub timestamp: i64,
+    /// The publisher's message counter.
+    pub clock: i64,
+}
+
+/// Closure invoked for every message received by a [`Subscriber`].
+pub type RawCallback = Box<dyn Fn(RawReceived<'_>) + Send + Sync + 'static>;
+
+/// Holds the closure registered with eCAL.
+///
+/// Replacing the closure takes the write lock, which waits for a running
+/// invocation, so a closure is never dropped while it is being called.
+#[derive(Default)]
+struct RawCallbackSlot {
+    callback: RwLock<Option<RawCallback>>,
+}
 
 /// A safe and ergonomic wrapper around the eCAL C subscriber API.
 ///
 /// This struct provides a high-level interface for subscribing to messages from
 /// a topic using eCAL. It manages the lifecycle of the underlying eCAL subscriber handle
-/// and allows registration of low-level C-compatible receive callbacks.
+/// and delivers the raw payloads to a Rust closure (see [`Subscriber::with_callback`])
+/// or to a low-level C-compatible receive callback.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustecal_core::types::DataTypeInfo;
+/// use rustecal_pubsub::{RawReceived, Subscriber};
+///
+/// let datatype = DataTypeInfo { encoding: String::new(), type_name: String::new(), descriptor: vec![] };
+/// let subscriber = Subscriber::with_callback("hello", datatype, |msg: RawReceived<'_>| {
+///     println!("{} bytes of {} on {}", msg.bytes.len(), msg.datatype.type_name, msg.topic_id.topic_name);
+/// }).unwrap();
+/// ```
+///
+/// # Thread Safety
+///
+/// `Subscriber` is `Send + Sync`, see the [crate documentation](crate#thread-safety).
 pub struct Subscriber {
     handle: *mut eCAL_Subscriber,
     _encoding: CString,
     _type_name: CString,
     _descriptor: Vec<u8>,
+    /// Closure slot passed to eCAL as user data; outlives the registration.
+    slot: Box<RawCallbackSlot>,
+    /// Keeps a guarded eCAL runtime alive while this handle exists.
+    _guard: Option<EcalGuard>,
 }
 
 impl Subscriber {
@@ -39,6 +93,43 @@ impl Subscriber {
             *mut c_void,
         ),
     ) -> Result<Self, String> {
+        let subscriber = Self::create(topic_name, data_type)?;
+
+        let result = unsafe {
+            eCAL_Subscriber_SetReceiveCallback(
+                subscriber.handle,
+                Some(callback),
+                ptr::null_mut(),
+            )
+        };
+
+        if result != 0 {
+            return Err("Failed to set receive callback".into());
+        }
+
+        Ok(subscriber)
+    }
+
+    /// Creates a new subscriber that passes every received message to `callback`.
+    ///
+    /// No decoding takes place, so this works for topics whose type is only
+    /// known at runtime, e.g. in recorders or bridges.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_name` - The name of the topic to subscribe to.
+    /// * `data_type` - Metadata describing the expected message format (encoding, type name, descriptor).
+    /// * `callback` - Invoked on the eCAL receive thread for every message.
+    pub fn with_callback<F>(topic_name: &str, data_type: DataTypeInfo, callback: F) -> Result<Self, String>
+    where
+        F: Fn(RawReceived<'_>) + Send + Sync + 'static,
+    {
+        let subscriber = Self::create(topic_name, data_type)?;
+        subscriber.set_callback(callback)?;
+        Ok(subscriber)
+    }
+
+    fn create(topic_name: &str, data_type: DataTypeInfo) -> Result<Self, String> {
         let c_topic = CString::new(topic_name).map_err(|_| "Invalid topic name")?;
         let c_encoding = CString::new(data_type.encoding).map_err(|_| "Invalid encoding")?;
         let c_type_name = CString::new(data_type.type_name).map_err(|_| "Invalid type name")?;
@@ -69,24 +160,55 @@ impl Subscriber {
             return Err("Failed to create eCAL_Subscriber".into());
         }
 
+        Ok(Self {
+            handle,
+            _encoding: c_encoding,
+            _type_name: c_type_name,
+            _descriptor: data_type.descriptor,
+            slot: Box::default(),
+            _guard: EcalGuard::current(),
+        })
+    }
+
+    /// Sets the closure invoked for every received message.
+    ///
+    /// Replaces a previously set closure or C callback. The closure runs on
+    /// the eCAL receive thread.
+    pub fn set_callback<F>(&self, callback: F) -> Result<(), String>
+    where
+        F: Fn(RawReceived<'_>) + Send + Sync + 'static,
+    {
+        let old = self
+            .slot
+            .callback
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(Box::new(callback));
+        // the previous closure is dropped outside of the lock
+        drop(old);
+
         let result = unsafe {
             eCAL_Subscriber_SetReceiveCallback(
-                handle,
-                Some(callback),
-                ptr::null_mut(),
+                self.handle,
+                Some(raw_trampoline),
+                &*self.slot as *const RawCallbackSlot as *mut c_void,
             )
         };
 
         if result != 0 {
-            return Err("Failed to set receive callback".into());
+            Err("Failed to set receive callback".into())
+        } else {
+            Ok(())
         }
+    }
 
-        Ok(Self {
-            handle,
-            _encoding: c_encoding,
-            _type_name: c_type_name,
-            _descriptor: data_type.descriptor,
-        })
+    /// Removes the receive callback; later messages are dropped.
+    pub fn remove_callback(&self) {
+        unsafe {
+            eCAL_Subscriber_RemoveReceiveCallback(self.handle);
+        }
+        let old = self.slot.callback.write().unwrap_or_else(|e| e.into_inner()).take();
+        drop(old);
     }
 
     /// Returns the raw pointer to the underlying eCAL subscriber.
@@ -175,8 +297,16 @@ impl Subscriber {
     }
 }
 
+// SAFETY: the eCAL C subscriber is internally synchronized, all other fields
+// are owned and only read after construction.
+unsafe impl Send for Subscriber {}
+unsafe impl Sync for Subscriber {}
+
 impl Drop for Subscriber {
     /// Cleans up and removes the callback, releasing the underlying eCAL subscriber handle.
+    ///
+    /// eCAL waits for a running callback to finish before the removal returns,
+    /// so the closure slot is freed safely afterwards.
     fn drop(&mut self) {
         unsafe {
             eCAL_Subscriber_RemoveReceiveCallback(self.handle);
@@ -184,3 +314,38 @@ impl Drop for Subscriber {
         }
     }
 }
+
+/// Passes a received message to the closure in the [`RawCallbackSlot`] given as user data.
+extern "C" fn raw_trampoline(
+    topic_id: *const eCAL_STopicId,
+    data_type_info: *const eCAL_SDataTypeInformation,
+    data: *const eCAL_SReceiveCallbackData,
+    user_data: *mut c_void,
+) {
+    unsafe {
+        if topic_id.is_null() || data_type_info.is_null() || data.is_null() || user_data.is_null() {
+            return;
+        }
+
+        let slot = &*(user_data as *const RawCallbackSlot);
+        let guard = slot.callback.read().unwrap_or_else(|e| e.into_inner());
+        let Some(callback) = guard.as_ref() else {
+            return;
+        };
+
+        let data = &*data;
+        let bytes = if data.buffer.is_null() || data.buffer_size == 0 {
+            &[]
+        } else {
+            slice::from_raw_parts(data.buffer as *const u8, data.buffer_size)
+        };
+
+        callback(RawReceived {
+            bytes,
+            topic_id: TopicId::from(*topic_id),
+            datatype: DataTypeInfo::from(*data_type_info),
+            timestamp: data.send_timestamp,
+            clock: data.send_clock,
+        });
+    }
+}
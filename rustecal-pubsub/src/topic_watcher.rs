@@ -0,0 +1,296 @@
+//! Subscribing to all topics that match a pattern.
+//!
+//! A [`TopicWatcher`] polls the eCAL monitoring data for publishers whose
+//! topic name matches one of its [`TopicPattern`]s. It creates an untyped
+//! [`Subscriber`] for every matching topic as it appears and drops it when the
+//! last publisher of the topic is gone. All messages are delivered to a single
+//! callback as [`RawReceived`], which carries the topic name.
+//!
+//! eCAL must be initialized with `EcalComponents::MONITORING` for topics to
+//! be discovered.
+
+use crate::subscriber::{RawReceived, Subscriber};
+use rustecal_core::monitoring::Monitoring;
+use rustecal_core::types::DataTypeInfo;
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// A topic name pattern.
+///
+/// - `*` matches any characters within one `/`-separated segment
+/// - `**` matches any characters, including `/`
+/// - `?` matches a single character other than `/`
+///
+/// All other characters match themselves, e.g. `sensors/*/imu` matches
+/// `sensors/front/imu` but not `sensors/front/left/imu`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicPattern {
+    pattern: Vec<char>,
+}
+
+impl TopicPattern {
+    /// Creates a pattern from its textual form.
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.chars().collect(),
+        }
+    }
+
+    /// Returns `true` if `topic_name` matches the pattern.
+    pub fn matches(&self, topic_name: &str) -> bool {
+        let name: Vec<char> = topic_name.chars().collect();
+        matches_from(&self.pattern, &name)
+    }
+}
+
+impl std::fmt::Display for TopicPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.pattern.iter().try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+fn matches_from(pattern: &[char], name: &[char]) -> bool {
+    match pattern {
+        [] => name.is_empty(),
+        ['*', '*', rest @ ..] => (0..=name.len()).any(|i| matches_from(rest, &name[i..])),
+        ['*', rest @ ..] => {
+            let segment = name.iter().position(|&c| c == '/').unwrap_or(name.len());
+            (0..=segment).any(|i| matches_from(rest, &name[i..]))
+        }
+        ['?', rest @ ..] => matches!(name.first(), Some(&c) if c != '/') && matches_from(rest, &name[1..]),
+        [p, rest @ ..] => name.first() == Some(p) && matches_from(rest, &name[1..]),
+    }
+}
+
+/// Callback receiving the messages of all watched topics.
+type WatchCallback = Arc<dyn Fn(RawReceived<'_>) + Send + Sync + 'static>;
+
+/// State shared with the polling thread.
+struct Shared {
+    patterns: Vec<TopicPattern>,
+    callback: WatchCallback,
+    subscribers: Mutex<HashMap<String, Subscriber>>,
+    stop: Mutex<bool>,
+    wake: Condvar,
+}
+
+impl Shared {
+    /// Subscribes to new matching topics and drops subscribers of vanished ones.
+    ///
+    /// Fails only if the monitoring data is unavailable; topics that cannot be
+    /// subscribed are returned as errors and retried on the next scan.
+    fn refresh(&self) -> Result<Vec<String>, String> {
+        let snapshot = Monitoring::get_snapshot().map_err(|e| e.to_string())?;
+
+        let mut published: HashMap<String, DataTypeInfo> = HashMap::new();
+        for publisher in snapshot.publishers {
+            if self.patterns.iter().any(|p| p.matches(&publisher.topic_name)) {
+                published
+                    .entry(publisher.topic_name)
+                    .or_insert(publisher.data_type);
+            }
+        }
+
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        let vanished: Vec<String> = subscribers
+            .keys()
+            .filter(|topic| !published.contains_key(*topic))
+            .cloned()
+            .collect();
+        let removed: Vec<Subscriber> = vanished
+            .iter()
+            .filter_map(|topic| subscribers.remove(topic))
+            .collect();
+
+        // a topic that cannot be subscribed is retried on the next scan
+        let mut errors = Vec::new();
+        for (topic_name, data_type) in published {
+            if subscribers.contains_key(&topic_name) {
+                continue;
+            }
+            let callback = self.callback.clone();
+            match Subscriber::with_callback(&topic_name, data_type, move |msg| callback(msg)) {
+                Ok(subscriber) => {
+                    subscribers.insert(topic_name, subscriber);
+                }
+                Err(e) => errors.push(format!("Failed to subscribe to '{}': {}", topic_name, e)),
+            }
+        }
+        drop(subscribers);
+
+        // unsubscribing waits for running callbacks, so do it without the lock
+        drop(removed);
+        Ok(errors)
+    }
+}
+
+/// Subscribes to every topic matching one of several patterns.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustecal_pubsub::TopicWatcher;
+///
+/// let watcher = TopicWatcher::new(&["sensors/*/imu"], |msg| {
+///     println!("{}: {} bytes", msg.topic_id.topic_name, msg.bytes.len());
+/// }).unwrap();
+/// ```
+///
+/// # Thread Safety
+///
+/// The callback is invoked concurrently from the receive threads of all
+/// subscribed topics and must be `Send + Sync`.
+pub struct TopicWatcher {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl TopicWatcher {
+    /// Default time between two scans of the monitoring data.
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Watches the given patterns, scanning for topics every second.
+    pub fn new<F>(patterns: &[&str], callback: F) -> Result<Self, String>
+    where
+        F: Fn(RawReceived<'_>) + Send + Sync + 'static,
+    {
+        Self::with_interval(patterns, Self::DEFAULT_INTERVAL, callback)
+    }
+
+    /// Watches the given patterns, scanning for topics every `interval`.
+    ///
+    /// The first scan runs before this function returns, so topics that are
+    /// already published are subscribed immediately. It fails only if the
+    /// monitoring data is unavailable; topics that cannot be subscribed yet
+    /// are retried on the next scan.
+    pub fn with_interval<F>(patterns: &[&str], interval: Duration, callback: F) -> Result<Self, String>
+    where
+        F: Fn(RawReceived<'_>) + Send + Sync + 'static,
+    {
+        let shared = Arc::new(Shared {
+            patterns: patterns.iter().map(|p| TopicPattern::new(p)).collect(),
+            callback: Arc::new(callback),
+            subscribers: Mutex::new(HashMap::new()),
+            stop: Mutex::new(false),
+            wake: Condvar::new(),
+        });
+        shared.refresh()?;
+
+        let thread = {
+            let shared = shared.clone();
+            std::thread::spawn(move || loop {
+                {
+                    let stop = shared.stop.lock().unwrap_or_else(|e| e.into_inner());
+                    let (stop, _) = shared
+                        .wake
+                        .wait_timeout_while(stop, interval, |stop| !*stop)
+                        .unwrap_or_else(|e| e.into_inner());
+                    if *stop {
+                        break;
+                    }
+                }
+                // errors are transient, e.g. while eCAL shuts down; retry next interval
+                let _ = shared.refresh();
+            })
+        };
+
+        Ok(Self {
+            shared,
+            thread: Some(thread),
+        })
+    }
+
+    /// Scans the monitoring data now instead of waiting for the next interval.
+    ///
+    /// Returns an error if the monitoring data is unavailable or a matching
+    /// topic could not be subscribed; the other topics are subscribed anyway.
+    pub fn refresh(&self) -> Result<(), String> {
+        let errors = self.shared.refresh()?;
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Returns the names of the currently subscribed topics, sorted.
+    pub fn topics(&self) -> Vec<String> {
+        let subscribers = self.shared.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut topics: Vec<String> = subscribers.keys().cloned().collect();
+        topics.sort();
+        topics
+    }
+
+    /// Returns the patterns this watcher matches topics against.
+    pub fn patterns(&self) -> &[TopicPattern] {
+        &self.shared.patterns
+    }
+}
+
+impl Drop for TopicWatcher {
+    /// Stops scanning and unsubscribes from all topics.
+    fn drop(&mut self) {
+        *self.shared.stop.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        self.shared.wake.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+
+        let subscribers = std::mem::take(&mut *self.shared.subscribers.lock().unwrap_or_else(|e| e.into_inner()));
+        drop(subscribers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_pattern_matches_itself_only() {
+        let pattern = TopicPattern::new("sensors/imu");
+        assert!(pattern.matches("sensors/imu"));
+        assert!(!pattern.matches("sensors/imu2"));
+        assert!(!pattern.matches("sensors"));
+    }
+
+    #[test]
+    fn star_stays_within_a_segment() {
+        let pattern = TopicPattern::new("sensors/*/imu");
+        assert!(pattern.matches("sensors/front/imu"));
+        assert!(pattern.matches("sensors//imu"));
+        assert!(!pattern.matches("sensors/front/left/imu"));
+
+        let pattern = TopicPattern::new("camera_*");
+        assert!(pattern.matches("camera_front"));
+        assert!(pattern.matches("camera_"));
+        assert!(!pattern.matches("camera_front/raw"));
+    }
+
+    #[test]
+    fn double_star_crosses_segments() {
+        let pattern = TopicPattern::new("sensors/**/imu");
+        assert!(pattern.matches("sensors/front/imu"));
+        assert!(pattern.matches("sensors/front/left/imu"));
+        assert!(!pattern.matches("sensors/front/gps"));
+
+        let pattern = TopicPattern::new("**");
+        assert!(pattern.matches(""));
+        assert!(pattern.matches("a/b/c"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character_except_slash() {
+        let pattern = TopicPattern::new("imu?");
+        assert!(pattern.matches("imu1"));
+        assert!(!pattern.matches("imu"));
+        assert!(!pattern.matches("imu12"));
+        assert!(!pattern.matches("imu/"));
+    }
+
+    #[test]
+    fn display_returns_the_pattern() {
+        assert_eq!(TopicPattern::new("sensors/**/imu?").to_string(), "sensors/**/imu?");
+    }
+}
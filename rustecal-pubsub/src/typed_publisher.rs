@@ -1,5 +1,5 @@
 use crate::publisher::Publisher;
-use rustecal_core::types::DataTypeInfo;
+use rustecal_core::types::{DataTypeInfo, EcalType};
 use crate::types::TopicId;
 use std::sync::Arc;
 use std::marker::PhantomData;
@@ -11,13 +11,10 @@ use std::marker::PhantomData;
 ///
 /// # Required Methods
 ///
-/// - [`datatype()`]: Returns metadata describing the encoding, type name,
+/// - [`EcalType::datatype()`]: Returns metadata describing the encoding, type name,
 ///   and optional descriptor (e.g., Protobuf schema).
-/// - [`to_bytes()`]: Serializes the message into a binary buffer.
-pub trait PublisherMessage {
-    /// Returns topic metadata for this message type.
-    fn datatype() -> DataTypeInfo;
-
+/// - [`to_bytes()`](PublisherMessage::to_bytes): Serializes the message into a binary buffer.
+pub trait PublisherMessage: EcalType {
     /// Serializes the message into a byte buffer for transmission.
     fn to_bytes(&self) -> Arc<[u8]>;
 }
@@ -53,8 +50,7 @@ impl<T: PublisherMessage> TypedPublisher<T> {
     ///
     /// Returns a `String` if the underlying eCAL publisher could not be created.
     pub fn new(topic_name: &str) -> Result<Self, String> {
-        let datatype = T::datatype();
-        let publisher = Publisher::new(topic_name, datatype)?;
+        let publisher = Publisher::new(topic_name, T::datatype_info().clone())?;
 
         Ok(Self {
             publisher,
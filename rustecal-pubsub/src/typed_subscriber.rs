@@ -1,19 +1,18 @@
 use crate::subscriber::Subscriber;
 use crate::types::TopicId;
-use rustecal_core::types::DataTypeInfo;
+use rustecal_core::types::{DataTypeInfo, EcalType};
 use rustecal_sys::{eCAL_SDataTypeInformation, eCAL_SReceiveCallbackData, eCAL_STopicId};
 use std::ffi::{c_void, CStr};
-use std::sync::Arc;
+use std::os::raw::c_char;
+use std::sync::{Arc, RwLock};
 use std::marker::PhantomData;
 use std::slice;
 
 /// Trait that must be implemented for any type used with [`TypedSubscriber`].
 ///
-/// Provides metadata and deserialization logic for a specific message type.
-pub trait SubscriberMessage: Sized {
-    /// Returns the metadata that describes this message type (encoding, name, optional descriptor).
-    fn datatype() -> DataTypeInfo;
-
+/// Provides deserialization logic for a specific message type; the metadata
+/// comes from [`EcalType`].
+pub trait SubscriberMessage: EcalType + Sized {
     /// Constructs an instance of the message type from a byte buffer and the accompanying DataTypeInfo.
     fn from_bytes(bytes: Arc<[u8]>, data_type_info: &DataTypeInfo) -> Option<Self>;
 }
@@ -39,23 +38,46 @@ pub struct Received<T> {
     pub clock: i64,
 }
 
-/// Internal trampoline wrapper that stores a type-erased callback for dispatching typed messages.
-struct CallbackWrapper<T: SubscriberMessage> {
-    callback: Box<dyn Fn(Received<T>) + Send + Sync>,
+/// Type-erased user callback for dispatching typed messages.
+type Callback<T> = Box<dyn Fn(Received<T>) + Send + Sync>;
+
+/// Internal slot holding the current user callback.
+///
+/// The slot is registered with eCAL exactly once and lives until the receive
+/// callback has been removed again. Replacing the callback takes the write
+/// lock, which waits for any invocation still running on the eCAL receive
+/// thread, so a closure is never dropped while it is being called.
+struct CallbackSlot<T: SubscriberMessage> {
+    callback: RwLock<Option<Callback<T>>>,
 }
 
-impl<T: SubscriberMessage> CallbackWrapper<T> {
-    fn new<F>(f: F) -> Self
-    where
-        F: Fn(Received<T>) + Send + Sync + 'static,
-    {
+impl<T: SubscriberMessage> CallbackSlot<T> {
+    fn new() -> Self {
         Self {
-            callback: Box::new(f),
+            callback: RwLock::new(None),
         }
     }
 
-    fn call(&self, received: Received<T>) {
-        (self.callback)(received);
+    fn set(&self, callback: Option<Callback<T>>) {
+        let old = {
+            let mut guard = self.callback.write().unwrap_or_else(|e| e.into_inner());
+            std::mem::replace(&mut *guard, callback)
+        };
+        // the previous closure is dropped outside of the lock
+        drop(old);
+    }
+
+    /// Calls the current callback with the message built by `receive`.
+    ///
+    /// `receive` only runs if a callback is set, so messages arriving while
+    /// no callback is registered are not decoded at all.
+    fn call_with(&self, receive: impl FnOnce() -> Option<Received<T>>) {
+        let guard = self.callback.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(callback) = guard.as_ref() {
+            if let Some(received) = receive() {
+                callback(received);
+            }
+        }
     }
 }
 
@@ -68,12 +90,16 @@ impl<T: SubscriberMessage> CallbackWrapper<T> {
 /// use rustecal::TypedSubscriber;
 /// use rustecal_types_string::StringMessage;
 ///
-/// let mut sub = TypedSubscriber::<StringMessage>::new("hello").unwrap();
+/// let sub = TypedSubscriber::<StringMessage>::new("hello").unwrap();
 /// sub.set_callback(|msg| println!("Received: {}", msg.msg.0));
 /// ```
+///
+/// # Thread Safety
+///
+/// `TypedSubscriber` is `Send + Sync`, see the [crate documentation](crate#thread-safety).
 pub struct TypedSubscriber<T: SubscriberMessage> {
     subscriber: Subscriber,
-    user_data: *mut CallbackWrapper<T>,
+    slot: *mut CallbackSlot<T>,
     _phantom: PhantomData<T>,
 }
 
@@ -88,17 +114,29 @@ impl<T: SubscriberMessage> TypedSubscriber<T> {
     ///
     /// `Ok(Self)` if the subscriber was created successfully, or `Err` with a description.
     pub fn new(topic_name: &str) -> Result<Self, String> {
-        let datatype = T::datatype();
+        let subscriber = Subscriber::new(topic_name, T::datatype_info().clone(), trampoline::<T>)?;
 
-        // Set dummy callback for construction, real callback will be assigned later
-        let boxed: Box<CallbackWrapper<T>> = Box::new(CallbackWrapper::new(|_| {}));
-        let user_data = Box::into_raw(boxed);
+        // Register the (initially empty) callback slot once; user callbacks are
+        // swapped inside the slot and never re-registered with eCAL.
+        let slot = Box::into_raw(Box::new(CallbackSlot::<T>::new()));
+        let result = unsafe {
+            rustecal_sys::eCAL_Subscriber_SetReceiveCallback(
+                subscriber.raw_handle(),
+                Some(trampoline::<T>),
+                slot as *mut _,
+            )
+        };
 
-        let subscriber = Subscriber::new(topic_name, datatype, trampoline::<T>)?;
+        if result != 0 {
+            unsafe {
+                drop(Box::from_raw(slot));
+            }
+            return Err("Failed to set receive callback".into());
+        }
 
         Ok(Self {
             subscriber,
-            user_data,
+            slot,
             _phantom: PhantomData,
         })
     }
@@ -106,29 +144,30 @@ impl<T: SubscriberMessage> TypedSubscriber<T> {
     /// Registers a user callback that receives a deserialized message with metadata.
     ///
     /// This replaces any previously set callback and transfers ownership of the closure.
+    /// If a message is currently being delivered to the previous callback, this call
+    /// waits until that invocation has returned before dropping it.
     ///
     /// # Arguments
     ///
     /// * `callback` - A closure accepting a [`Received<T>`] message.
-    pub fn set_callback<F>(&mut self, callback: F)
+    pub fn set_callback<F>(&self, callback: F)
     where
         F: Fn(Received<T>) + Send + Sync + 'static,
     {
-        unsafe {
-            // Drop the old callback
-            let _ = Box::from_raw(self.user_data);
-        }
+        self.slot().set(Some(Box::new(callback)));
+    }
 
-        let boxed = Box::new(CallbackWrapper::new(callback));
-        self.user_data = Box::into_raw(boxed);
+    /// Removes the current user callback.
+    ///
+    /// Incoming messages are discarded until a new callback is set with
+    /// [`TypedSubscriber::set_callback`].
+    pub fn remove_callback(&self) {
+        self.slot().set(None);
+    }
 
-        unsafe {
-            rustecal_sys::eCAL_Subscriber_SetReceiveCallback(
-                self.subscriber.raw_handle(),
-                Some(trampoline::<T>),
-                self.user_data as *mut _,
-            );
-        }
+    fn slot(&self) -> &CallbackSlot<T> {
+        // SAFETY: the slot is allocated in `new` and only freed on drop.
+        unsafe { &*self.slot }
     }
 
     /// Returns the number of currently connected publishers to this topic.
@@ -164,12 +203,20 @@ impl<T: SubscriberMessage> TypedSubscriber<T> {
     }
 }
 
+// SAFETY: `slot` is an owned box holding a `Send + Sync` callback behind a
+// `RwLock`; it is only freed on drop, after eCAL stopped calling into it.
+unsafe impl<T: SubscriberMessage> Send for TypedSubscriber<T> {}
+unsafe impl<T: SubscriberMessage> Sync for TypedSubscriber<T> {}
+
 impl<T: SubscriberMessage> Drop for TypedSubscriber<T> {
     /// Cleans up and removes the callback, releasing any boxed closures.
+    ///
+    /// eCAL waits for a running callback to finish before the removal returns,
+    /// so the slot can be freed safely afterwards.
     fn drop(&mut self) {
         unsafe {
             rustecal_sys::eCAL_Subscriber_RemoveReceiveCallback(self.subscriber.raw_handle());
-            let _ = Box::from_raw(self.user_data);
+            drop(Box::from_raw(self.slot));
         }
     }
 }
@@ -177,6 +224,7 @@ impl<T: SubscriberMessage> Drop for TypedSubscriber<T> {
 /// Internal trampoline for dispatching incoming messages to the registered user closure.
 ///
 /// Converts C FFI types into Rust-safe [`Received<T>`] values and passes them to the callback.
+/// Messages arriving while no callback is set are dropped without decoding.
 extern "C" fn trampoline<T: SubscriberMessage>(
     topic_id: *const eCAL_STopicId,
     data_type_info: *const eCAL_SDataTypeInformation,
@@ -184,34 +232,51 @@ extern "C" fn trampoline<T: SubscriberMessage>(
     user_data: *mut c_void,
 ) {
     unsafe {
-        if data.is_null() || user_data.is_null() {
+        if data.is_null() || data_type_info.is_null() || user_data.is_null() {
             return;
         }
-        // Raw payload buffer
-        let msg_slice = slice::from_raw_parts((*data).buffer as *const u8, (*data).buffer_size);
-        let msg_arc: Arc<[u8]> = Arc::from(msg_slice);
-        // Build Rust DataTypeInfo from eCAL metadata
-        let encoding = CStr::from_ptr((*data_type_info).encoding).to_string_lossy().into_owned();
-        let type_name = CStr::from_ptr((*data_type_info).name).to_string_lossy().into_owned();
-        let descriptor = if (*data_type_info).descriptor.is_null() || (*data_type_info).descriptor_length == 0 {
-            Vec::new()
-        } else {
-            slice::from_raw_parts((*data_type_info).descriptor as *const u8, (*data_type_info).descriptor_length as usize).to_vec()
-        };
-        let dt_info = DataTypeInfo { encoding, type_name, descriptor };
-        // Deserialize with access to datatype information
-        if let Some(decoded) = T::from_bytes(msg_arc.clone(), &dt_info) {
-            let cb_wrapper = &*(user_data as *const CallbackWrapper<T>);
-            let topic_name = CStr::from_ptr((*topic_id).topic_name).to_string_lossy().into_owned();
-            let metadata = Received {
+        let slot = &*(user_data as *const CallbackSlot<T>);
+        slot.call_with(|| {
+            // Raw payload buffer
+            let msg_slice = if (*data).buffer.is_null() || (*data).buffer_size == 0 {
+                &[]
+            } else {
+                slice::from_raw_parts((*data).buffer as *const u8, (*data).buffer_size)
+            };
+            let msg_arc: Arc<[u8]> = Arc::from(msg_slice);
+            // Build Rust DataTypeInfo from eCAL metadata
+            let encoding = cstr_to_string((*data_type_info).encoding);
+            let type_name = cstr_to_string((*data_type_info).name);
+            let descriptor = if (*data_type_info).descriptor.is_null() || (*data_type_info).descriptor_length == 0 {
+                Vec::new()
+            } else {
+                slice::from_raw_parts((*data_type_info).descriptor as *const u8, (*data_type_info).descriptor_length).to_vec()
+            };
+            let dt_info = DataTypeInfo { encoding, type_name, descriptor };
+            // Deserialize with access to datatype information
+            let decoded = T::from_bytes(msg_arc, &dt_info)?;
+            let topic_name = if topic_id.is_null() {
+                String::new()
+            } else {
+                cstr_to_string((*topic_id).topic_name)
+            };
+            Some(Received {
                 payload: decoded,
                 topic_name,
-                encoding: dt_info.encoding.clone(),
-                type_name: dt_info.type_name.clone(),
+                encoding: dt_info.encoding,
+                type_name: dt_info.type_name,
                 timestamp: (*data).send_timestamp,
                 clock: (*data).send_clock,
-            };
-            cb_wrapper.call(metadata);
-        }
+            })
+        });
+    }
+}
+
+/// Helper to safely convert a null-terminated C string.
+fn cstr_to_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() }
     }
 }
@@ -0,0 +1,123 @@
+//! Replaces and removes typed subscriber callbacks while messages flow.
+//!
+//! This test needs a local eCAL installation, like the samples.
+
+use rustecal_core::types::{DataTypeInfo, EcalType};
+use rustecal_core::{EcalComponents, EcalGuard};
+use rustecal_pubsub::{PublisherMessage, SubscriberMessage, TypedPublisher, TypedSubscriber};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+struct Counter(u64);
+
+impl EcalType for Counter {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            encoding: "raw".into(),
+            type_name: "counter".into(),
+            descriptor: vec![],
+        }
+    }
+}
+
+impl PublisherMessage for Counter {
+    fn to_bytes(&self) -> Arc<[u8]> {
+        Arc::from(self.0.to_le_bytes())
+    }
+}
+
+impl SubscriberMessage for Counter {
+    fn from_bytes(bytes: Arc<[u8]>, _data_type_info: &DataTypeInfo) -> Option<Self> {
+        Some(Counter(u64::from_le_bytes(bytes.as_ref().try_into().ok()?)))
+    }
+}
+
+/// Ids of the closures that have been dropped.
+#[derive(Default)]
+struct Dropped(Mutex<HashSet<usize>>);
+
+impl Dropped {
+    fn contains(&self, id: usize) -> bool {
+        self.0.lock().unwrap().contains(&id)
+    }
+}
+
+/// Captured by every callback; records when its closure is dropped.
+struct Tracker {
+    id: usize,
+    dropped: Arc<Dropped>,
+}
+
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        self.dropped.0.lock().unwrap().insert(self.id);
+    }
+}
+
+#[test]
+fn replace_and_remove_callback_while_receiving() {
+    let _ecal = EcalGuard::new(Some("rustecal_callback_replacement"), EcalComponents::DEFAULT).unwrap();
+
+    let subscriber = TypedSubscriber::<Counter>::new("callback_replacement").unwrap();
+    let publisher = Arc::new(TypedPublisher::<Counter>::new("callback_replacement").unwrap());
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while subscriber.get_publisher_count() == 0 {
+        assert!(Instant::now() < deadline, "publisher did not connect");
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    let sender = {
+        let publisher = publisher.clone();
+        let running = running.clone();
+        thread::spawn(move || {
+            let mut n = 0;
+            while running.load(Ordering::SeqCst) {
+                publisher.send(&Counter(n));
+                n += 1;
+            }
+        })
+    };
+
+    let dropped = Arc::new(Dropped::default());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_into_dropped = Arc::new(AtomicUsize::new(0));
+
+    // keep swapping until some messages made it through, at least 2000 times
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut id = 0;
+    while id < 2_000 || (calls.load(Ordering::SeqCst) == 0 && Instant::now() < deadline) {
+        let tracker = Tracker { id, dropped: dropped.clone() };
+        let calls = calls.clone();
+        let calls_into_dropped = calls_into_dropped.clone();
+        subscriber.set_callback(move |_| {
+            // checked before and after the call's work, so a drop racing
+            // with the invocation is noticed as well
+            if tracker.dropped.contains(tracker.id) {
+                calls_into_dropped.fetch_add(1, Ordering::SeqCst);
+            }
+            calls.fetch_add(1, Ordering::SeqCst);
+            if tracker.dropped.contains(tracker.id) {
+                calls_into_dropped.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        if id % 3 == 0 {
+            subscriber.remove_callback();
+        }
+        id += 1;
+    }
+
+    running.store(false, Ordering::SeqCst);
+    sender.join().unwrap();
+    drop(subscriber);
+
+    // every closure has been dropped exactly once, none was called afterwards
+    assert_eq!(dropped.0.lock().unwrap().len(), id);
+    assert_eq!(calls_into_dropped.load(Ordering::SeqCst), 0);
+    assert!(calls.load(Ordering::SeqCst) > 0);
+}
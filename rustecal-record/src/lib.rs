@@ -0,0 +1,22 @@
+//! # rustecal-record
+//!
+//! Records eCAL topics to MCAP files.
+//!
+//! ## Features
+//! - Records a set of topics, topic patterns, or all discovered topics.
+//! - One MCAP channel per topic; schemas from the topic's `DataTypeInfo`.
+//! - Send timestamps and publisher clocks are kept with every message.
+//! - File rotation by size and time.
+//!
+//! ## Key Types
+//! - `Recorder`: subscribes to matching topics and writes what it receives
+//! - `McapWriter`: writes `RawReceived` messages, usable on its own
+//! - `Rotation`: when to continue in a new file
+//!
+//! The crate also builds the `rustecal-record` command line recorder.
+
+pub mod recorder;
+pub mod writer;
+
+pub use recorder::Recorder;
+pub use writer::{McapWriter, Rotation};
@@ -0,0 +1,91 @@
+//! `rustecal-record`: records eCAL topics to MCAP files until stopped with Ctrl+C.
+
+use clap::Parser;
+use rustecal_core::{Ecal, EcalComponents};
+use rustecal_record::{McapWriter, Recorder, Rotation};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Record eCAL topics to MCAP files.
+#[derive(Parser)]
+#[command(name = "rustecal-record", version)]
+struct Args {
+    /// Topic names or patterns (`*`, `**`, `?`) to record; all topics if omitted
+    patterns: Vec<String>,
+
+    /// Output file; with rotation, files get a running index appended
+    #[arg(short, long, default_value = "recording.mcap")]
+    output: PathBuf,
+
+    /// Continue in a new file after this many megabytes of payload
+    #[arg(long, value_name = "MB")]
+    max_size: Option<u64>,
+
+    /// Continue in a new file after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    max_duration: Option<u64>,
+
+    /// Stop recording after this many seconds
+    #[arg(short, long, value_name = "SECONDS")]
+    duration: Option<u64>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let patterns: Vec<&str> = if args.patterns.is_empty() {
+        vec!["**"]
+    } else {
+        args.patterns.iter().map(String::as_str).collect()
+    };
+    let max_bytes = args
+        .max_size
+        .map(|mb| mb.checked_mul(1024 * 1024).ok_or("--max-size is too large"))
+        .transpose()?;
+    let rotation = Rotation {
+        max_bytes,
+        max_duration: args.max_duration.map(Duration::from_secs),
+    };
+
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = running.clone();
+        ctrlc::set_handler(move || running.store(false, Ordering::SeqCst))?;
+    }
+
+    // topics are discovered through the monitoring layer
+    Ecal::initialize(
+        Some("rustecal record"),
+        EcalComponents::DEFAULT | EcalComponents::MONITORING,
+    )?;
+
+    let recorder = match Recorder::new(&patterns, McapWriter::with_rotation(&args.output, rotation)) {
+        Ok(recorder) => recorder,
+        Err(e) => {
+            Ecal::finalize();
+            return Err(e.into());
+        }
+    };
+    println!("Recording topics matching {:?}, press Ctrl+C to stop...", patterns);
+
+    let start = Instant::now();
+    let deadline = args.duration.map(Duration::from_secs);
+    let mut topics = Vec::new();
+    while running.load(Ordering::SeqCst) && Ecal::ok() && deadline.is_none_or(|d| start.elapsed() < d) {
+        let current = recorder.topics();
+        if current != topics {
+            println!("recording {:?}", current);
+            topics = current;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    let message_count = recorder.message_count();
+    let result = recorder.stop();
+    Ecal::finalize();
+
+    let files = result?;
+    println!("Recorded {} messages to {:?}", message_count, files);
+    Ok(())
+}
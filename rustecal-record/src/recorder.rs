@@ -0,0 +1,92 @@
+//! Recording all topics that match a set of patterns.
+
+use crate::writer::McapWriter;
+use rustecal_pubsub::TopicWatcher;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// State shared with the receive callbacks.
+struct State {
+    writer: McapWriter,
+    /// The first write error, reported by [`Recorder::stop`].
+    error: Option<String>,
+}
+
+/// Records every topic matching one of several patterns to MCAP.
+///
+/// Topics are discovered through a [`TopicWatcher`], so eCAL must be
+/// initialized with `EcalComponents::MONITORING`. Pass `"**"` to record all
+/// topics, or plain topic names to record exactly those.
+///
+/// # Example
+///
+/// ```no_run
+/// use rustecal_record::{McapWriter, Recorder};
+///
+/// let recorder = Recorder::new(&["**"], McapWriter::new("recording.mcap")).unwrap();
+/// std::thread::sleep(std::time::Duration::from_secs(10));
+/// let files = recorder.stop().unwrap();
+/// ```
+pub struct Recorder {
+    // declared first so it is dropped, and stops calling back, before the writer
+    watcher: TopicWatcher,
+    state: Arc<Mutex<State>>,
+}
+
+impl Recorder {
+    /// Starts recording the topics matching `patterns` with `writer`.
+    pub fn new(patterns: &[&str], writer: McapWriter) -> Result<Self, String> {
+        let state = Arc::new(Mutex::new(State { writer, error: None }));
+
+        let watcher = {
+            let state = state.clone();
+            TopicWatcher::new(patterns, move |msg| {
+                let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                if let Err(e) = state.writer.write(&msg) {
+                    state.error.get_or_insert(e);
+                }
+            })?
+        };
+
+        Ok(Self { watcher, state })
+    }
+
+    /// Returns the names of the topics currently recorded, sorted.
+    pub fn topics(&self) -> Vec<String> {
+        self.watcher.topics()
+    }
+
+    /// Returns the number of messages written so far.
+    pub fn message_count(&self) -> u64 {
+        self.lock().writer.message_count()
+    }
+
+    /// Returns the paths of the files written so far, including the current one.
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.lock().writer.files().to_vec()
+    }
+
+    /// Stops recording, finishes the current file and returns the paths of
+    /// all files written.
+    ///
+    /// Returns the first error that occurred while writing, if any.
+    pub fn stop(self) -> Result<Vec<PathBuf>, String> {
+        let Recorder { watcher, state } = self;
+        drop(watcher);
+
+        // the watcher held the only other reference
+        let state = match Arc::try_unwrap(state) {
+            Ok(state) => state.into_inner().unwrap_or_else(|e| e.into_inner()),
+            Err(_) => return Err("Recorder is still in use".into()),
+        };
+        let files = state.writer.finish()?;
+        match state.error {
+            Some(e) => Err(e),
+            None => Ok(files),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
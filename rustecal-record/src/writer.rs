@@ -0,0 +1,272 @@
+//! Writing received messages to MCAP files.
+//!
+//! Every topic becomes an MCAP channel. The channel's schema carries the
+//! type name and descriptor of the topic's `DataTypeInfo`; the eCAL encoding
+//! and type name are additionally stored in the channel metadata, so the
+//! original `DataTypeInfo` can be restored on playback.
+//!
+//! eCAL encodings are mapped to MCAP's well-known encodings where one exists
+//! (`proto` becomes `protobuf`, whose schema is the serialized
+//! `FileDescriptorSet` eCAL already uses as descriptor); other encodings are
+//! written unchanged.
+
+use mcap::records::MessageHeader;
+use mcap::Writer;
+use rustecal_core::types::DataTypeInfo;
+use rustecal_pubsub::RawReceived;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Channel metadata key holding the eCAL encoding of the topic.
+pub const ENCODING_KEY: &str = "ecal.encoding";
+
+/// Channel metadata key holding the eCAL type name of the topic.
+pub const TYPE_NAME_KEY: &str = "ecal.type_name";
+
+/// When to close the current file and continue in a new one.
+///
+/// With rotation enabled, files are named after the output path with a
+/// running index, e.g. `recording_000.mcap`, `recording_001.mcap`, ...
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rotation {
+    /// Rotate once this many payload bytes were written to the current file.
+    pub max_bytes: Option<u64>,
+    /// Rotate once the current file has been open this long.
+    pub max_duration: Option<Duration>,
+}
+
+impl Rotation {
+    /// Returns `true` if files are rotated at all.
+    pub fn is_enabled(&self) -> bool {
+        self.max_bytes.is_some() || self.max_duration.is_some()
+    }
+
+    fn is_due(&self, bytes: u64, open_for: Duration) -> bool {
+        self.max_bytes.is_some_and(|max| bytes >= max) || self.max_duration.is_some_and(|max| open_for >= max)
+    }
+}
+
+/// A channel of the current file.
+struct Channel {
+    encoding: String,
+    type_name: String,
+    id: u16,
+}
+
+/// The file currently written.
+struct OpenFile {
+    writer: Writer<BufWriter<File>>,
+    /// Channels by topic name; a topic gets one channel per datatype seen.
+    channels: HashMap<String, Vec<Channel>>,
+    schemas: HashMap<(String, String, Vec<u8>), u16>,
+    opened: Instant,
+    bytes: u64,
+}
+
+impl OpenFile {
+    fn create(path: &Path) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("Failed to create '{}': {}", path.display(), e))?;
+        let writer = Writer::new(BufWriter::new(file)).map_err(|e| e.to_string())?;
+        Ok(Self {
+            writer,
+            channels: HashMap::new(),
+            schemas: HashMap::new(),
+            opened: Instant::now(),
+            bytes: 0,
+        })
+    }
+
+    /// Returns the channel of `topic_name` with `datatype`, adding it on first use.
+    fn channel(&mut self, topic_name: &str, datatype: &DataTypeInfo) -> Result<u16, String> {
+        let known = self.channels.get(topic_name).and_then(|channels| {
+            channels
+                .iter()
+                .find(|c| c.encoding == datatype.encoding && c.type_name == datatype.type_name)
+                .map(|c| c.id)
+        });
+        if let Some(id) = known {
+            return Ok(id);
+        }
+
+        let schema_id = self.schema(datatype)?;
+        let metadata = BTreeMap::from([
+            (ENCODING_KEY.to_string(), datatype.encoding.clone()),
+            (TYPE_NAME_KEY.to_string(), datatype.type_name.clone()),
+        ]);
+        let id = self
+            .writer
+            .add_channel(schema_id, topic_name, mcap_encoding(&datatype.encoding), &metadata)
+            .map_err(|e| e.to_string())?;
+
+        self.channels.entry(topic_name.to_string()).or_default().push(Channel {
+            encoding: datatype.encoding.clone(),
+            type_name: datatype.type_name.clone(),
+            id,
+        });
+        Ok(id)
+    }
+
+    /// Returns the schema for `datatype`, or 0 (no schema) if it has neither
+    /// a type name nor a descriptor.
+    fn schema(&mut self, datatype: &DataTypeInfo) -> Result<u16, String> {
+        if datatype.type_name.is_empty() && datatype.descriptor.is_empty() {
+            return Ok(0);
+        }
+
+        let key = (
+            datatype.type_name.clone(),
+            datatype.encoding.clone(),
+            datatype.descriptor.clone(),
+        );
+        if let Some(&id) = self.schemas.get(&key) {
+            return Ok(id);
+        }
+
+        let id = self
+            .writer
+            .add_schema(&datatype.type_name, mcap_encoding(&datatype.encoding), &datatype.descriptor)
+            .map_err(|e| e.to_string())?;
+        self.schemas.insert(key, id);
+        Ok(id)
+    }
+}
+
+/// Writes received messages to one or more MCAP files.
+///
+/// Files are finished when rotated, on [`McapWriter::finish`] and on drop.
+pub struct McapWriter {
+    path: PathBuf,
+    rotation: Rotation,
+    current: Option<OpenFile>,
+    files: Vec<PathBuf>,
+    message_count: u64,
+}
+
+impl McapWriter {
+    /// Creates a writer for `path`, which is created with the first message.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self::with_rotation(path, Rotation::default())
+    }
+
+    /// Creates a writer that rotates files according to `rotation`.
+    pub fn with_rotation(path: impl Into<PathBuf>, rotation: Rotation) -> Self {
+        Self {
+            path: path.into(),
+            rotation,
+            current: None,
+            files: Vec::new(),
+            message_count: 0,
+        }
+    }
+
+    /// Writes one message to the current file, rotating first if it is due.
+    ///
+    /// The receive time becomes the MCAP log time and the publisher's send
+    /// timestamp the publish time; the publisher's clock is the sequence number.
+    pub fn write(&mut self, msg: &RawReceived<'_>) -> Result<(), String> {
+        if let Some(file) = &self.current {
+            if self.rotation.is_due(file.bytes, file.opened.elapsed()) {
+                self.close()?;
+            }
+        }
+
+        let file = match &mut self.current {
+            Some(file) => file,
+            None => {
+                let path = self.next_path();
+                let file = OpenFile::create(&path)?;
+                self.files.push(path);
+                self.current.insert(file)
+            }
+        };
+
+        let channel_id = file.channel(&msg.topic_id.topic_name, &msg.datatype)?;
+        let header = MessageHeader {
+            channel_id,
+            // MCAP sequence numbers are 32 bit; larger publisher clocks wrap
+            // around on purpose, which keeps gaps between messages visible
+            sequence: u32::try_from(msg.clock).unwrap_or(msg.clock.rem_euclid(1 << 32) as u32),
+            log_time: now_ns(),
+            publish_time: (msg.timestamp.max(0) as u64).saturating_mul(1000),
+        };
+        file.writer
+            .write_to_known_channel(&header, msg.bytes)
+            .map_err(|e| e.to_string())?;
+
+        file.bytes += msg.bytes.len() as u64;
+        self.message_count += 1;
+        Ok(())
+    }
+
+    /// Finishes the current file and returns the paths of all files written.
+    pub fn finish(mut self) -> Result<Vec<PathBuf>, String> {
+        self.close()?;
+        Ok(std::mem::take(&mut self.files))
+    }
+
+    /// Returns the paths of all files written so far, including the current one.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Returns the number of messages written.
+    pub fn message_count(&self) -> u64 {
+        self.message_count
+    }
+
+    fn close(&mut self) -> Result<(), String> {
+        match self.current.take() {
+            Some(mut file) => file.writer.finish().map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Path of the next file: the output path itself, or with rotation the
+    /// output path with a running index appended to the file stem.
+    fn next_path(&self) -> PathBuf {
+        if !self.rotation.is_enabled() {
+            return self.path.clone();
+        }
+
+        let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match self.path.extension() {
+            Some(ext) => format!("{}_{:03}.{}", stem, self.files.len(), ext.to_string_lossy()),
+            None => format!("{}_{:03}", stem, self.files.len()),
+        };
+        self.path.with_file_name(name)
+    }
+}
+
+impl Drop for McapWriter {
+    /// Finishes the current file.
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+/// Maps an eCAL encoding to the corresponding MCAP encoding.
+pub fn mcap_encoding(encoding: &str) -> &str {
+    match encoding {
+        "proto" => "protobuf",
+        other => other,
+    }
+}
+
+/// Maps an MCAP encoding back to the corresponding eCAL encoding.
+pub fn ecal_encoding(encoding: &str) -> &str {
+    match encoding {
+        "protobuf" => "proto",
+        other => other,
+    }
+}
+
+/// Current time in nanoseconds since the Unix epoch.
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
@@ -7,7 +7,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ecal::initialize(Some("blob receive rust"), EcalComponents::DEFAULT)
         .expect("eCAL initialization failed");
 
-    let mut subscriber = TypedSubscriber::<BytesMessage>::new("blob")?;
+    let subscriber = TypedSubscriber::<BytesMessage>::new("blob")?;
 
     subscriber.set_callback(|msg: Received<BytesMessage>| {
         let buffer = &msg.payload.data;
@@ -0,0 +1,37 @@
+use rustecal::{Ecal, EcalComponents, EcalMessage, TypedPublisher};
+
+// Published as is, without a JsonMessage wrapper; serde_receive can receive it
+#[derive(serde::Serialize, serde::Deserialize, EcalMessage, Clone, Debug)]
+#[ecal(format = "json")]
+struct SimpleMessage {
+    message: String,
+    count: u64,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize eCAL
+    Ecal::initialize(Some("derive send rust"), EcalComponents::DEFAULT)
+        .expect("eCAL initialization failed");
+
+    // Create a typed publisher for topic "simple_message"
+    let publisher: TypedPublisher<SimpleMessage> = TypedPublisher::new("simple_message")?;
+
+    let mut count = 0u64;
+    while Ecal::ok() {
+        count += 1;
+        let message = SimpleMessage {
+            count,
+            message: "HELLO WORLD FROM RUST".into(),
+        };
+
+        // Send over eCAL pub/sub
+        publisher.send(&message);
+        println!("Sent: message = {}, count = {}", message.message, message.count);
+
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+
+    // Clean up and finalize eCAL
+    Ecal::finalize();
+    Ok(())
+}
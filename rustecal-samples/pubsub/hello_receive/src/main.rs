@@ -7,7 +7,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ecal::initialize(Some("hello receive rust"), EcalComponents::DEFAULT)
         .expect("eCAL initialization failed");
 
-    let mut subscriber = TypedSubscriber::<StringMessage>::new("hello")?;
+    let subscriber = TypedSubscriber::<StringMessage>::new("hello")?;
 
     subscriber.set_callback(|msg: Received<StringMessage>| {
         println!("------------------------------------------");
@@ -14,7 +14,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ecal::initialize(Some("person receive rust"), EcalComponents::DEFAULT)
         .expect("eCAL initialization failed");
 
-    let mut subscriber = TypedSubscriber::<ProtobufMessage<Person>>::new("person")?;
+    let subscriber = TypedSubscriber::<ProtobufMessage<Person>>::new("person")?;
 
     subscriber.set_callback(|msg: Received<ProtobufMessage<Person>>| {
         let person = msg.payload.data;
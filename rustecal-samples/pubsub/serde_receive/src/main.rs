@@ -14,7 +14,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .expect("eCAL initialization failed");
 
     // Create a typed subscriber for topic "simple_message"
-    let mut subscriber: TypedSubscriber<JsonMessage<SimpleMessage>> =
+    let subscriber: TypedSubscriber<JsonMessage<SimpleMessage>> =
         TypedSubscriber::new("simple_message")?;
 
     subscriber.set_callback(|msg: Received<JsonMessage<SimpleMessage>>| {
@@ -0,0 +1,47 @@
+use rustecal::{Ecal, EcalComponents, RawReceived, TopicWatcher};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Topic patterns to watch, e.g. `watch_receive "sensors/*/imu" hello`
+    let patterns: Vec<String> = std::env::args().skip(1).collect();
+    let patterns: Vec<&str> = if patterns.is_empty() {
+        vec!["**"]
+    } else {
+        patterns.iter().map(String::as_str).collect()
+    };
+
+    // Initialize eCAL; topics are discovered through the monitoring layer
+    Ecal::initialize(
+        Some("watch receive rust"),
+        EcalComponents::DEFAULT | EcalComponents::MONITORING,
+    )
+    .expect("eCAL initialization failed");
+
+    let watcher = TopicWatcher::new(&patterns, |msg: RawReceived| {
+        println!(
+            "{:<30} {:>8} bytes  {} ({})  clock {}",
+            msg.topic_id.topic_name,
+            msg.bytes.len(),
+            msg.datatype.type_name,
+            msg.datatype.encoding,
+            msg.clock,
+        );
+    })?;
+
+    println!("Watching topics matching {:?}...", patterns);
+
+    // keep the thread alive so callbacks can run
+    let mut topics = Vec::new();
+    while Ecal::ok() {
+        let current = watcher.topics();
+        if current != topics {
+            println!("subscribed topics: {:?}", current);
+            topics = current;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+
+    // clean up and finalize eCAL
+    drop(watcher);
+    Ecal::finalize();
+    Ok(())
+}
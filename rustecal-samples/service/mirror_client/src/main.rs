@@ -1,5 +1,5 @@
 use rustecal::{Ecal, EcalComponents};
-use rustecal::{ServiceClient, ServiceRequest, CallState};
+use rustecal::{ServiceClient, ServiceRequest, CallState, ClientMethod};
 use std::thread;
 use std::time::Duration;
 
@@ -8,7 +8,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ecal::initialize(Some("mirror client rust"), EcalComponents::DEFAULT)
         .expect("eCAL initialization failed");
 
-    let client = ServiceClient::new("mirror")?;
+    let client = ServiceClient::with_methods(
+        "mirror",
+        vec![ClientMethod::new("echo"), ClientMethod::new("reverse")],
+    )?;
 
     // Wait until connected
     while client.get_client_instances().is_empty() {
@@ -34,27 +37,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Method '{}' called with message: stressed", method_name);
 
             match response {
-                Some(res) => {
-                    match CallState::from(res.success as i32) {
-                        CallState::Executed => {
-                            let text = String::from_utf8_lossy(&res.payload);
-                            println!(
-                                "Received response: {} from service id {:?}",
-                                text, res.server_id.service_id.entity_id
-                            );
-                        }
-                        CallState::Failed => {
-                            println!(
-                                "Received error: {} from service id {:?}",
-                                res.error_msg.unwrap_or_else(|| "Unknown".into()),
-                                res.server_id.service_id.entity_id
-                            );
-                        }
-                        _ => {}
+                Ok(res) => match res.call_state {
+                    CallState::Executed if res.is_success() => {
+                        let text = String::from_utf8_lossy(&res.payload);
+                        println!(
+                            "Received response: {} from service id {:?} on {} (pid {})",
+                            text, res.server_id.service_id.entity_id, res.server_id.service_id.host_name, res.server_id.service_id.process_id
+                        );
                     }
-                }
-                None => {
-                    println!("Method blocking call failed ..");
+                    CallState::Executed | CallState::Failed => {
+                        println!(
+                            "Received error: {} from service id {:?} on {} (pid {})",
+                            res.error_msg.unwrap_or_else(|| "Unknown".into()),
+                            res.server_id.service_id.entity_id,
+                            res.server_id.service_id.host_name,
+                            res.server_id.service_id.process_id
+                        );
+                    }
+                    _ => {}
+                },
+                Err(err) => {
+                    println!("Method blocking call failed: {}", err);
                 }
             }
         }
@@ -1,5 +1,5 @@
 use rustecal::{Ecal, EcalComponents};
-use rustecal::{ServiceClient, ServiceRequest};
+use rustecal::{ServiceClient, ServiceRequest, ClientMethod};
 use std::thread;
 use std::time::Duration;
 
@@ -8,7 +8,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ecal::initialize(Some("mirror client instances rust"), EcalComponents::DEFAULT)
         .expect("eCAL initialization failed");
 
-    let client = ServiceClient::new("mirror")?;
+    let client = ServiceClient::with_methods(
+        "mirror",
+        vec![ClientMethod::new("echo"), ClientMethod::new("reverse")],
+    )?;
+
     let methods = ["echo", "reverse"];
     let mut i = 0;
 
@@ -35,23 +39,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 );
 
                 match response {
-                    Some(res) => {
-                        if res.success {
+                    Ok(res) => {
+                        if res.is_success() {
                             println!(
-                                "Received response: {} from service id {:?}",
+                                "Received response: {} from service id {:?} on {}",
                                 String::from_utf8_lossy(&res.payload),
-                                res.server_id.service_id.entity_id
+                                res.server_id.service_id.entity_id,
+                                res.server_id.service_id.host_name
                             );
                         } else {
                             println!(
-                                "Received error: {} from service id {:?}",
+                                "Received error: {} from service id {:?} on {}",
                                 res.error_msg.unwrap_or_else(|| "Unknown".into()),
-                                res.server_id.service_id.entity_id
+                                res.server_id.service_id.entity_id,
+                                res.server_id.service_id.host_name
                             );
                         }
                     }
-                    None => {
-                        println!("Call failed or timed out.");
+                    Err(err) => {
+                        println!("Call failed: {}", err);
                     }
                 }
             }
@@ -0,0 +1,14 @@
+use rustecal_types_protobuf::EcalServiceGenerator;
+use std::path::PathBuf;
+
+fn main() {
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+
+    prost_build::Config::new()
+        .file_descriptor_set_path(out_dir.join("mirror_descriptor.bin"))
+        .service_generator(Box::new(
+            EcalServiceGenerator::new().with_descriptor_set("mirror_descriptor.bin"),
+        ))
+        .compile_protos(&["proto/mirror.proto"], &["proto"])
+        .unwrap();
+}
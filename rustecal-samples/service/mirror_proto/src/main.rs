@@ -0,0 +1,45 @@
+use rustecal::{Ecal, EcalComponents, ServiceError, ServiceServer};
+use std::thread;
+use std::time::Duration;
+
+mod mirror { include!(concat!(env!("OUT_DIR"), "/pb.mirror.rs")); }
+
+use mirror::{MirrorRequest, MirrorResponse, MirrorService, MirrorServiceClient, MirrorServiceServer};
+
+struct Mirror;
+
+impl MirrorService for Mirror {
+    fn echo(&self, request: MirrorRequest) -> Result<MirrorResponse, ServiceError> {
+        Ok(MirrorResponse { text: request.text })
+    }
+
+    fn reverse(&self, request: MirrorRequest) -> Result<MirrorResponse, ServiceError> {
+        Ok(MirrorResponse { text: request.text.chars().rev().collect() })
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize eCAL
+    Ecal::initialize(Some("mirror proto rust"), EcalComponents::DEFAULT)
+        .expect("eCAL initialization failed");
+
+    // Serve under the protobuf service name, so C++ clients find it as well
+    let server = ServiceServer::new(MirrorServiceServer::SERVICE_NAME)?;
+    MirrorServiceServer::register(&server, Mirror)?;
+
+    // Call it through the generated typed client
+    let client = MirrorServiceClient::new(MirrorServiceServer::SERVICE_NAME)?.with_timeout(Some(1000));
+    client.client().wait_for_server(Duration::from_secs(5));
+
+    while Ecal::ok() {
+        let request = MirrorRequest { text: "stressed".into() };
+        println!("echo    : {:?}", client.echo(&request).map(|r| r.text));
+        println!("reverse : {:?}\n", client.reverse(&request).map(|r| r.text));
+
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    // clean up and finalize eCAL
+    Ecal::finalize();
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use rustecal::{Ecal, EcalComponents};
+use rustecal::{rpc, ServiceError, ServiceServer};
+use std::thread;
+use std::time::Duration;
+
+// The trait defines the service contract; server and client are generated from it
+#[rpc(format = "json")]
+pub trait Mirror {
+    fn echo(&self, text: String) -> String;
+    fn reverse(&self, text: String) -> String;
+    fn repeat(&self, text: String, times: u32) -> Result<String, ServiceError>;
+}
+
+struct MirrorService;
+
+impl Mirror for MirrorService {
+    fn echo(&self, text: String) -> String {
+        text
+    }
+
+    fn reverse(&self, text: String) -> String {
+        text.chars().rev().collect()
+    }
+
+    fn repeat(&self, text: String, times: u32) -> Result<String, ServiceError> {
+        if times > 10 {
+            return Err(ServiceError::new("too many repetitions"));
+        }
+        Ok(text.repeat(times as usize))
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize eCAL
+    Ecal::initialize(Some("mirror rpc rust"), EcalComponents::DEFAULT)
+        .expect("eCAL initialization failed");
+
+    // Serve the trait implementation
+    let server = ServiceServer::new("mirror_rpc")?;
+    MirrorServer::register(&server, MirrorService)?;
+
+    // Call it through the generated typed client
+    let client = MirrorClient::new("mirror_rpc")?.with_timeout(Some(1000));
+    client.client().wait_for_server(Duration::from_secs(5));
+
+    while Ecal::ok() {
+        println!("echo    : {:?}", client.echo("stressed".into()));
+        println!("reverse : {:?}", client.reverse("stressed".into()));
+        println!("repeat  : {:?}", client.repeat("ab".into(), 3));
+        println!("repeat  : {:?}\n", client.repeat("ab".into(), 100));
+
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    // clean up and finalize eCAL
+    Ecal::finalize();
+    Ok(())
+}
@@ -7,14 +7,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .expect("eCAL initialization failed");
 
     // Create the service server named "mirror"
-    let mut server = ServiceServer::new("mirror")?;
+    let server = ServiceServer::new("mirror")?;
 
     // Register "echo" method: respond with request unchanged
     server.add_method("echo", Box::new(|info: MethodInfo, req: &[u8]| {
         println!("Method   : '{}' called", info.method_name);
         println!("Request  : {}", String::from_utf8_lossy(req));
         println!("Response : {}\n", String::from_utf8_lossy(req));
-        req.to_vec()
+        Ok(req.to_vec())
     }))?;
 
     // Register "reverse" method: respond with request reversed
@@ -24,7 +24,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("Method   : '{}' called", info.method_name);
         println!("Request  : {}", String::from_utf8_lossy(req));
         println!("Response : {}\n", String::from_utf8_lossy(&reversed));
-        reversed
+        Ok(reversed)
     }))?;
 
     println!("Rust mirror service running. Press Ctrl+C to exit.");
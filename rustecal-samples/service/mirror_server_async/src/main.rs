@@ -0,0 +1,61 @@
+use rustecal::{Ecal, EcalComponents};
+use rustecal::{AsyncMethodOptions, MethodInfo, ServiceServer};
+use std::time::Duration;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize eCAL
+    Ecal::initialize(Some("mirror server async rust"), EcalComponents::DEFAULT)
+        .expect("eCAL initialization failed");
+
+    // Runtime driving the async method handlers
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    // Create the service server named "mirror"
+    let server = ServiceServer::new("mirror")?;
+
+    // Cancel calls running longer than one second, run at most four at once
+    let options = AsyncMethodOptions::default()
+        .with_timeout(Duration::from_secs(1))
+        .with_max_concurrency(4);
+
+    // Register "echo" method: respond with request unchanged
+    server.add_async_method(
+        "echo",
+        runtime.handle().clone(),
+        options.clone(),
+        |info: MethodInfo, req: Vec<u8>| async move {
+            // simulate some asynchronous work (database, HTTP, ...)
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            println!("Method   : '{}' called", info.method_name);
+            println!("Request  : {}", String::from_utf8_lossy(&req));
+            println!("Response : {}\n", String::from_utf8_lossy(&req));
+            Ok(req)
+        },
+    )?;
+
+    // Register "reverse" method: respond with request reversed
+    server.add_async_method(
+        "reverse",
+        runtime.handle().clone(),
+        options,
+        |info: MethodInfo, req: Vec<u8>| async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let mut reversed = req.clone();
+            reversed.reverse();
+            println!("Method   : '{}' called", info.method_name);
+            println!("Request  : {}", String::from_utf8_lossy(&req));
+            println!("Response : {}\n", String::from_utf8_lossy(&reversed));
+            Ok(reversed)
+        },
+    )?;
+
+    println!("Rust async mirror service running. Press Ctrl+C to exit.");
+
+    while Ecal::ok() {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    // clean up and finalize eCAL
+    Ecal::finalize();
+    Ok(())
+}
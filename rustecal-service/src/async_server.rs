@@ -0,0 +1,133 @@
+//! Async method handlers for [`ServiceServer`] (requires the `async` feature).
+//!
+//! eCAL invokes method callbacks synchronously on its own worker threads.
+//! [`ServiceServer::add_async_method`] spawns the handler future on a
+//! user-supplied tokio runtime and blocks the eCAL thread until the future has
+//! completed, timed out, or failed.
+
+use crate::server::ServiceServer;
+use crate::types::{MethodInfo, ServiceError};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
+
+/// Execution limits for an async method.
+#[derive(Debug, Clone, Default)]
+pub struct AsyncMethodOptions {
+    /// Maximum duration of a single call, including the time it waits for a
+    /// free slot. Calls exceeding it are cancelled and reported to the client
+    /// as failed. `None` means no limit.
+    pub timeout: Option<Duration>,
+    /// Maximum number of calls of this method running at the same time.
+    /// Further calls wait for a free slot. `None` means no limit.
+    pub max_concurrency: Option<usize>,
+}
+
+impl AsyncMethodOptions {
+    /// Sets the call timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of concurrently running calls.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+}
+
+impl ServiceServer {
+    /// Registers an async method callback under the given name.
+    ///
+    /// Every call spawns the future returned by `callback` on `runtime`.
+    /// The handler receives an owned copy of the request, so it can be held
+    /// across `.await` points.
+    /// The runtime must drive its tasks on its own, e.g. a multi-threaded
+    /// runtime, as the eCAL worker thread only waits for the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - The method name.
+    /// * `runtime` - Handle of the tokio runtime driving the handler futures.
+    /// * `options` - Timeout and concurrency limit for this method.
+    /// * `callback` - Async handler returning the response payload or a [`ServiceError`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustecal_service::{ServiceServer, AsyncMethodOptions};
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<(), String> {
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let server = ServiceServer::new("mirror")?;
+    ///
+    /// server.add_async_method(
+    ///     "echo",
+    ///     runtime.handle().clone(),
+    ///     AsyncMethodOptions::default().with_timeout(Duration::from_secs(1)),
+    ///     |_info, request| async move { Ok(request) },
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_async_method<F, Fut>(
+        &self,
+        method: &str,
+        runtime: Handle,
+        options: AsyncMethodOptions,
+        callback: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(MethodInfo, Vec<u8>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<u8>, ServiceError>> + Send + 'static,
+    {
+        let callback = Arc::new(callback);
+        let limit = options.max_concurrency.map(|n| Arc::new(Semaphore::new(n.max(1))));
+        let timeout = options.timeout;
+
+        self.add_method(
+            method,
+            Box::new(move |info: MethodInfo, request: &[u8]| {
+                let callback = callback.clone();
+                let limit = limit.clone();
+                let request = request.to_vec();
+
+                let task = runtime.spawn(async move {
+                    let call = async move {
+                        // Wait for a free slot; the permit is held until the call has finished
+                        let _permit = match limit {
+                            Some(limit) => Some(
+                                limit
+                                    .acquire_owned()
+                                    .await
+                                    .map_err(|_| ServiceError::new("method is shutting down"))?,
+                            ),
+                            None => None,
+                        };
+                        callback(info, request).await
+                    };
+
+                    // The timeout covers waiting for a slot as well, as the
+                    // eCAL worker thread is blocked for the whole time
+                    match timeout {
+                        Some(timeout) => tokio::time::timeout(timeout, call)
+                            .await
+                            .map_err(|_| {
+                                ServiceError::new(format!("method timed out after {:?}", timeout))
+                            })?,
+                        None => call.await,
+                    }
+                });
+
+                // eCAL worker threads are not runtime threads, so blocking here is fine
+                runtime
+                    .block_on(task)
+                    .map_err(|e| ServiceError::new(format!("method task failed: {}", e)))?
+            }),
+        )
+    }
+}
@@ -0,0 +1,157 @@
+//! Selection of a single server when several servers offer the same service.
+//!
+//! [`ServiceClient::call`] broadcasts to every connected server.
+//! [`ServiceClient::call_with_strategy`] instead picks one
+//! [`ClientInstance`] according to a [`CallStrategy`] and calls only that one.
+
+use crate::client::ServiceClient;
+use crate::client_instance::ClientInstance;
+use crate::response::ServiceResponse;
+use crate::retry::random_u64;
+use crate::types::{CallError, ServiceRequest};
+use rustecal_core::types::EntityId;
+use rustecal_core::Process;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How [`ServiceClient::call_with_strategy`] selects the server to call.
+///
+/// Servers are ordered by their entity id before selecting, so the order is
+/// the same in every process and does not depend on discovery order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallStrategy {
+    /// Cycles through the servers, one server per call.
+    RoundRobin,
+    /// Picks a random server for every call.
+    Random,
+    /// Calls all servers at once and returns the first successful response.
+    FirstResponding,
+    /// Round-robin over servers on this host; falls back to all servers if
+    /// none runs locally.
+    PreferLocalHost,
+    /// Calls the server with the given entity id only.
+    Entity(EntityId),
+}
+
+impl ServiceClient {
+    /// Calls `method` on one server selected by `strategy`.
+    ///
+    /// Returns [`CallError::NoServer`] if no server is connected, or if the
+    /// server requested by [`CallStrategy::Entity`] is not available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustecal_service::{ServiceClient, ServiceRequest, CallStrategy};
+    ///
+    /// let client = ServiceClient::new("mirror").unwrap();
+    /// let request = ServiceRequest { payload: b"stressed".to_vec() };
+    /// let response = client.call_with_strategy("reverse", request, Some(1000), &CallStrategy::RoundRobin);
+    /// ```
+    pub fn call_with_strategy(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+        strategy: &CallStrategy,
+    ) -> Result<ServiceResponse, CallError> {
+        if !self.is_method_declared(method) {
+            return Err(CallError::InvalidMethod(method.to_string()));
+        }
+
+        let mut instances: Vec<(EntityId, ClientInstance)> = self
+            .get_client_instances()
+            .into_iter()
+            .filter_map(|instance| instance.get_client_id().map(|id| (id, instance)))
+            .collect();
+        instances.sort_by_key(|(id, _)| id.entity_id);
+
+        if instances.is_empty() {
+            return Err(CallError::NoServer(self.service_name().to_string()));
+        }
+
+        let instance = match strategy {
+            CallStrategy::RoundRobin => self.next_round_robin(instances),
+            CallStrategy::Random => {
+                let index = random_index(instances.len());
+                instances.swap_remove(index).1
+            }
+            CallStrategy::FirstResponding => {
+                let instances = instances.into_iter().map(|(_, instance)| instance).collect();
+                return first_responding(instances, method, request, timeout_ms);
+            }
+            CallStrategy::PreferLocalHost => {
+                let host_name = Process::get_host_name();
+                let (local, remote): (Vec<_>, Vec<_>) = instances
+                    .into_iter()
+                    .partition(|(id, _)| id.host_name == host_name);
+                self.next_round_robin(if local.is_empty() { remote } else { local })
+            }
+            CallStrategy::Entity(entity) => instances
+                .into_iter()
+                .find(|(id, _)| id.entity_id == entity.entity_id)
+                .map(|(_, instance)| instance)
+                .ok_or_else(|| CallError::NoServer(self.service_name().to_string()))?,
+        };
+
+        instance.call(method, request, timeout_ms)
+    }
+
+    fn next_round_robin(&self, mut instances: Vec<(EntityId, ClientInstance)>) -> ClientInstance {
+        let index = self.next_instance.fetch_add(1, Ordering::Relaxed) % instances.len();
+        instances.swap_remove(index).1
+    }
+}
+
+/// Returns a pseudo-random index below `len`.
+fn random_index(len: usize) -> usize {
+    (random_u64() % len as u64) as usize
+}
+
+/// Calls all instances concurrently and returns the first successful response.
+///
+/// If no server succeeds, the last response or error received is returned.
+fn first_responding(
+    instances: Vec<ClientInstance>,
+    method: &str,
+    request: ServiceRequest,
+    timeout_ms: Option<i32>,
+) -> Result<ServiceResponse, CallError> {
+    let count = instances.len();
+    let (tx, rx) = mpsc::channel();
+
+    // The calls run detached, so a slow server does not delay the result
+    for instance in instances {
+        let tx = tx.clone();
+        let method = method.to_string();
+        let request = request.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(instance.call(&method, request, timeout_ms));
+        });
+    }
+    drop(tx);
+
+    let deadline = timeout_ms.map(|t| Instant::now() + Duration::from_millis(t.max(0) as u64));
+    let mut last = Err(CallError::Timeout);
+
+    for _ in 0..count {
+        let result = match deadline {
+            Some(deadline) => match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(result) => result,
+                Err(_) => break,
+            },
+            None => match rx.recv() {
+                Ok(result) => result,
+                Err(_) => break,
+            },
+        };
+
+        if matches!(&result, Ok(response) if response.is_success()) {
+            return result;
+        }
+        last = result;
+    }
+
+    last
+}
@@ -1,38 +1,228 @@
 use rustecal_sys::*;
-use crate::client_instance::ClientInstance;
-use crate::types::ServiceRequest;
+use rustecal_core::EcalGuard;
+use crate::client_event::{self, ClientEvent, ClientEventSlot};
+use crate::client_instance::{ClientInstance, ClientInstanceList};
+use crate::types::{CallError, CallState, ClientMethod, ServiceId, ServiceRequest};
 use crate::response::ServiceResponse;
 use std::ffi::CString;
 use std::os::raw::c_void;
 use std::ptr;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+/// Represents a client that calls methods of a named eCAL service.
+///
+/// # Thread Safety
+///
+/// `ServiceClient` is `Send + Sync`, see the [crate documentation](crate#thread-safety).
 pub struct ServiceClient {
     pub(crate) handle: *mut eCAL_ServiceClient,
+    service_name: String,
+    methods: Vec<ClientMethod>,
+    /// Position of the next server for round-robin call strategies.
+    pub(crate) next_instance: AtomicUsize,
+    events: Arc<ClientEventSlot>,
+    event_registration: u64,
+    /// Keeps a guarded eCAL runtime alive while this handle exists.
+    _guard: Option<EcalGuard>,
 }
 
 impl ServiceClient {
+    /// Creates a client for `service_name` without declaring its methods.
+    ///
+    /// Any method name may be called; monitoring shows no methods for this client.
     pub fn new(service_name: &str) -> Result<Self, String> {
+        Self::with_methods(service_name, Vec::new())
+    }
+
+    /// Creates a client that declares the methods it calls.
+    ///
+    /// The methods and their request/response types are registered with eCAL
+    /// and appear in the monitoring layer. Calls to methods that were not
+    /// declared are rejected without contacting a server.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustecal_service::{ServiceClient, ClientMethod};
+    ///
+    /// let client = ServiceClient::with_methods(
+    ///     "mirror",
+    ///     vec![ClientMethod::new("echo"), ClientMethod::new("reverse")],
+    /// ).unwrap();
+    /// ```
+    pub fn with_methods(service_name: &str, methods: Vec<ClientMethod>) -> Result<Self, String> {
         let c_service = CString::new(service_name).map_err(|_| "Invalid service name")?;
-        let handle = unsafe { eCAL_ServiceClient_New(c_service.as_ptr(), ptr::null(), 0, None) };
+
+        // The C strings must outlive the eCAL_ServiceClient_New call
+        let mut c_strings = Vec::with_capacity(methods.len() * 5);
+        for method in &methods {
+            c_strings.push(CString::new(method.method_name.as_str()).map_err(|_| "Invalid method name")?);
+            for datatype in [&method.request_type, &method.response_type] {
+                c_strings.push(CString::new(datatype.type_name.as_str()).map_err(|_| "Invalid type name")?);
+                c_strings.push(CString::new(datatype.encoding.as_str()).map_err(|_| "Invalid encoding string")?);
+            }
+        }
+
+        let method_infos: Vec<eCAL_SServiceMethodInformation> = methods
+            .iter()
+            .zip(c_strings.chunks(5))
+            .map(|(method, c)| eCAL_SServiceMethodInformation {
+                method_name: c[0].as_ptr(),
+                request_type: Self::raw_datatype(&c[1], &c[2], &method.request_type.descriptor),
+                response_type: Self::raw_datatype(&c[3], &c[4], &method.response_type.descriptor),
+            })
+            .collect();
+
+        let method_infos_ptr = if method_infos.is_empty() {
+            ptr::null()
+        } else {
+            method_infos.as_ptr()
+        };
+
+        // Register for events before creating the client, so early connects are not missed
+        let events = Arc::new(ClientEventSlot::default());
+        let event_registration = client_event::register(service_name, events.clone());
+
+        let handle = unsafe {
+            eCAL_ServiceClient_New(
+                c_service.as_ptr(),
+                method_infos_ptr,
+                method_infos.len(),
+                Some(client_event::client_event_trampoline),
+            )
+        };
 
         if handle.is_null() {
+            client_event::unregister(event_registration);
             Err("Failed to create eCAL_ServiceClient".into())
         } else {
-            Ok(Self { handle })
+            Ok(Self {
+                handle,
+                service_name: service_name.to_string(),
+                methods,
+                next_instance: AtomicUsize::new(0),
+                events,
+                event_registration,
+                _guard: EcalGuard::current(),
+            })
         }
     }
 
-    pub fn call(&self, method: &str, request: ServiceRequest, timeout_ms: Option<i32>) -> Option<ServiceResponse> {
-        self.call_all(method, request, timeout_ms)?.pop()
+    pub(crate) fn raw_datatype(type_name: &CString, encoding: &CString, descriptor: &[u8]) -> eCAL_SDataTypeInformation {
+        eCAL_SDataTypeInformation {
+            name: type_name.as_ptr(),
+            encoding: encoding.as_ptr(),
+            descriptor: if descriptor.is_empty() {
+                ptr::null()
+            } else {
+                descriptor.as_ptr() as *const c_void
+            },
+            descriptor_length: descriptor.len(),
+        }
     }
 
-    pub fn call_all(
+    /// Returns the name of the service this client calls.
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// Returns the id of this client.
+    pub fn get_service_id(&self) -> Option<ServiceId> {
+        unsafe {
+            let raw = eCAL_ServiceClient_GetServiceId(self.handle);
+            raw.as_ref().map(|id| ServiceId::from_ffi(id))
+        }
+    }
+
+    /// Returns the methods declared at construction.
+    pub fn methods(&self) -> &[ClientMethod] {
+        &self.methods
+    }
+
+    /// Returns `true` if `method` may be called on this client, i.e. no
+    /// methods were declared or `method` is one of them.
+    pub fn is_method_declared(&self, method: &str) -> bool {
+        self.methods.is_empty() || self.methods.iter().any(|m| m.method_name == method)
+    }
+
+    /// Sets a callback that is invoked when servers of this service connect,
+    /// disconnect, or time out. Replaces any previously set callback.
+    ///
+    /// To consume events elsewhere, forward them into a channel:
+    ///
+    /// ```no_run
+    /// use rustecal_service::{ServiceClient, ClientEvent};
+    /// use std::sync::{mpsc, Mutex};
+    ///
+    /// let client = ServiceClient::new("mirror").unwrap();
+    /// let (tx, rx) = mpsc::channel::<ClientEvent>();
+    /// let tx = Mutex::new(tx);
+    /// client.set_event_callback(move |event| {
+    ///     let _ = tx.lock().unwrap().send(event.clone());
+    /// });
+    /// ```
+    pub fn set_event_callback<F>(&self, callback: F)
+    where
+        F: Fn(&ClientEvent) + Send + Sync + 'static,
+    {
+        self.events.set(Some(Box::new(callback)));
+    }
+
+    /// Removes the event callback.
+    pub fn remove_event_callback(&self) {
+        self.events.set(None);
+    }
+
+    /// Returns `true` if at least one server of this service is connected.
+    pub fn is_connected(&self) -> bool {
+        unsafe { eCAL_ServiceClient_IsConnected(self.handle) != 0 }
+    }
+
+    /// Returns the number of servers this client currently knows about.
+    pub fn server_count(&self) -> usize {
+        self.get_client_instances().len()
+    }
+
+    /// Blocks until a server is connected or `timeout` has elapsed.
+    ///
+    /// Returns `true` if a server is connected.
+    pub fn wait_for_server(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self.is_connected() {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Calls `method` on all connected servers and returns their responses.
+    ///
+    /// A response is returned for every server that answered, including
+    /// servers whose method failed; check [`ServiceResponse::is_success`] or
+    /// the [`CallState`](crate::types::CallState) of each. An error is
+    /// returned if no response was received at all.
+    pub fn call(
         &self,
         method: &str,
         request: ServiceRequest,
         timeout_ms: Option<i32>,
-    ) -> Option<Vec<ServiceResponse>> {
-        let c_method = CString::new(method).ok()?;
+    ) -> Result<Vec<ServiceResponse>, CallError> {
+        if !self.is_method_declared(method) {
+            return Err(CallError::InvalidMethod(method.to_string()));
+        }
+
+        let c_method =
+            CString::new(method).map_err(|_| CallError::InvalidMethod(method.to_string()))?;
+
+        if !self.is_connected() {
+            return Err(CallError::NoServer(self.service_name.clone()));
+        }
 
         let mut response_ptr: *mut eCAL_SServiceResponse = ptr::null_mut();
         let mut response_len: usize = 0;
@@ -42,7 +232,9 @@ impl ServiceClient {
             .map(|t| t as *const i32)
             .unwrap_or(ptr::null());
 
-        let result = unsafe {
+        // A non-zero result only means that not every server succeeded, so the
+        // individual responses are evaluated regardless.
+        unsafe {
             eCAL_ServiceClient_CallWithResponse(
                 self.handle,
                 c_method.as_ptr(),
@@ -54,22 +246,34 @@ impl ServiceClient {
             )
         };
 
-        if result != 0 || response_ptr.is_null() || response_len == 0 {
-            return None;
-        }
-
         let mut responses = Vec::with_capacity(response_len);
 
-        unsafe {
-            for i in 0..response_len {
-                let item = &*response_ptr.add(i);
-                responses.push(ServiceResponse::from_struct(item));
+        if !response_ptr.is_null() {
+            unsafe {
+                for i in 0..response_len {
+                    let item = &*response_ptr.add(i);
+                    responses.push(ServiceResponse::from_struct(item));
+                }
+
+                eCAL_Free(response_ptr as *mut c_void);
             }
+        }
+
+        // eCAL reports timeouts per server in the responses, so an empty list
+        // means the servers went away or the call failed on the transport level
+        if responses.is_empty() {
+            return Err(if self.is_connected() {
+                CallError::Failed("no server responded".into())
+            } else {
+                CallError::NoServer(self.service_name.clone())
+            });
+        }
 
-            eCAL_Free(response_ptr as *mut c_void);
+        if responses.iter().all(|r| r.call_state == CallState::Timeout) {
+            return Err(CallError::Timeout);
         }
 
-        Some(responses)
+        Ok(responses)
     }
 
     pub fn get_client_instances(&self) -> Vec<ClientInstance> {
@@ -81,6 +285,10 @@ impl ServiceClient {
                 return result;
             }
 
+            // The instances are owned by the list, so every instance keeps it
+            // alive until the last one is dropped.
+            let list = Arc::new(ClientInstanceList::from_raw(list_ptr));
+
             let mut offset = 0;
             loop {
                 let instance_ptr = *list_ptr.add(offset);
@@ -88,21 +296,24 @@ impl ServiceClient {
                     break;
                 }
 
-                result.push(ClientInstance::from_raw(instance_ptr));
+                result.push(ClientInstance::from_list(instance_ptr, list.clone()));
                 offset += 1;
             }
-
-            eCAL_ClientInstances_Delete(list_ptr);
         }
 
         result
     }
 }
 
+// SAFETY: the eCAL C client is internally synchronized.
+unsafe impl Send for ServiceClient {}
+unsafe impl Sync for ServiceClient {}
+
 impl Drop for ServiceClient {
     fn drop(&mut self) {
         unsafe {
             eCAL_ServiceClient_Delete(self.handle);
         }
+        client_event::unregister(self.event_registration);
     }
 }
@@ -0,0 +1,135 @@
+//! Connection events of a [`ServiceClient`](crate::ServiceClient).
+//!
+//! eCAL reports client events through a plain C function pointer without user
+//! data. A single trampoline is therefore registered for every client, and
+//! events are routed to all clients of the same service in this process.
+
+use crate::types::ServiceId;
+use rustecal_sys::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Kind of a client event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientEventKind {
+    /// A server of the service became available.
+    Connected,
+    /// A server of the service went away.
+    Disconnected,
+    /// A call to a server timed out.
+    Timeout,
+    /// Any other event code reported by eCAL.
+    Unknown(i32),
+}
+
+impl From<i32> for ClientEventKind {
+    fn from(value: i32) -> Self {
+        match value {
+            x if x == eCAL_eClientEvent_eCAL_eClientEvent_connected as i32 => ClientEventKind::Connected,
+            x if x == eCAL_eClientEvent_eCAL_eClientEvent_disconnected as i32 => ClientEventKind::Disconnected,
+            x if x == eCAL_eClientEvent_eCAL_eClientEvent_timeout as i32 => ClientEventKind::Timeout,
+            other => ClientEventKind::Unknown(other),
+        }
+    }
+}
+
+impl From<u32> for ClientEventKind {
+    fn from(value: u32) -> Self {
+        ClientEventKind::from(value as i32)
+    }
+}
+
+/// An event reported to a service client.
+#[derive(Debug, Clone)]
+pub struct ClientEvent {
+    /// What happened.
+    pub kind: ClientEventKind,
+    /// The server the event refers to.
+    pub server_id: ServiceId,
+    /// Event time in microseconds.
+    pub time: i64,
+}
+
+/// The client event callback signature.
+pub type ClientEventCallback = Box<dyn Fn(&ClientEvent) + Send + Sync + 'static>;
+
+/// Holds the event callback of one client.
+#[derive(Default)]
+pub(crate) struct ClientEventSlot {
+    callback: RwLock<Option<ClientEventCallback>>,
+}
+
+impl ClientEventSlot {
+    pub(crate) fn set(&self, callback: Option<ClientEventCallback>) {
+        *self.callback.write().unwrap_or_else(|e| e.into_inner()) = callback;
+    }
+
+    fn call(&self, event: &ClientEvent) {
+        if let Some(cb) = self.callback.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            cb(event);
+        }
+    }
+}
+
+/// A client registered for event delivery.
+struct Listener {
+    id: u64,
+    service_name: String,
+    slot: Arc<ClientEventSlot>,
+}
+
+static LISTENERS: Mutex<Vec<Listener>> = Mutex::new(Vec::new());
+static NEXT_LISTENER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Registers an event slot for `service_name` and returns its registration id.
+pub(crate) fn register(service_name: &str, slot: Arc<ClientEventSlot>) -> u64 {
+    let id = NEXT_LISTENER_ID.fetch_add(1, Ordering::Relaxed);
+    LISTENERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Listener {
+            id,
+            service_name: service_name.to_string(),
+            slot,
+        });
+    id
+}
+
+/// Removes a registration created by [`register`].
+pub(crate) fn unregister(id: u64) {
+    LISTENERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|l| l.id != id);
+}
+
+/// Trampoline passed to `eCAL_ServiceClient_New`.
+pub(crate) unsafe extern "C" fn client_event_trampoline(
+    service_id: *const eCAL_SServiceId,
+    data: *const eCAL_SClientEventCallbackData,
+) {
+    if service_id.is_null() || data.is_null() {
+        return;
+    }
+
+    let event = unsafe {
+        ClientEvent {
+            kind: ClientEventKind::from((*data).type_),
+            server_id: ServiceId::from_ffi(&*service_id),
+            time: (*data).time,
+        }
+    };
+
+    // Collect the matching slots first, so callbacks run without the registry lock
+    let slots: Vec<Arc<ClientEventSlot>> = LISTENERS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .filter(|l| l.service_name == event.server_id.service_name)
+        .map(|l| l.slot.clone())
+        .collect();
+
+    for slot in slots {
+        slot.call(&event);
+    }
+}
@@ -1,26 +1,69 @@
-use crate::types::{ServiceId, ServiceRequest};
+use crate::types::{CallError, CallState, ServiceRequest};
 use crate::response::ServiceResponse;
+use rustecal_core::types::EntityId;
 use rustecal_sys::*;
 use std::ffi::CString;
 use std::os::raw::c_void;
+use std::sync::Arc;
 
+/// Owns a null-terminated instance list returned by
+/// `eCAL_ServiceClient_GetClientInstances`.
+///
+/// Deleting the list also deletes the instances it contains.
+#[derive(Debug)]
+pub(crate) struct ClientInstanceList {
+    list: *mut *mut eCAL_ClientInstance,
+}
+
+impl ClientInstanceList {
+    pub(crate) fn from_raw(list: *mut *mut eCAL_ClientInstance) -> Self {
+        Self { list }
+    }
+}
+
+// SAFETY: the list is only read after construction and freed once on drop.
+unsafe impl Send for ClientInstanceList {}
+unsafe impl Sync for ClientInstanceList {}
+
+impl Drop for ClientInstanceList {
+    fn drop(&mut self) {
+        unsafe {
+            eCAL_ClientInstances_Delete(self.list);
+        }
+    }
+}
+
+/// A connection from a [`ServiceClient`](crate::ServiceClient) to one specific server.
+///
+/// # Thread Safety
+///
+/// `ClientInstance` is `Send + Sync`, see the [crate documentation](crate#thread-safety).
 #[derive(Debug)]
 pub struct ClientInstance {
     pub(crate) instance: *mut eCAL_ClientInstance,
+    _list: Option<Arc<ClientInstanceList>>,
 }
 
 impl ClientInstance {
+    /// Wraps a raw instance pointer whose lifetime is managed by the caller.
     pub fn from_raw(raw: *mut eCAL_ClientInstance) -> Self {
-        Self { instance: raw }
+        Self { instance: raw, _list: None }
     }
 
+    /// Wraps an instance owned by `list`, keeping the list alive.
+    pub(crate) fn from_list(raw: *mut eCAL_ClientInstance, list: Arc<ClientInstanceList>) -> Self {
+        Self { instance: raw, _list: Some(list) }
+    }
+
+    /// Calls `method` on this server only.
     pub fn call(
         &self,
         method: &str,
         request: ServiceRequest,
         timeout_ms: Option<i32>,
-    ) -> Option<ServiceResponse> {
-        let c_method = CString::new(method).ok()?;
+    ) -> Result<ServiceResponse, CallError> {
+        let c_method =
+            CString::new(method).map_err(|_| CallError::InvalidMethod(method.to_string()))?;
         let timeout_ptr = timeout_ms
             .as_ref()
             .map(|t| t as *const i32)
@@ -37,21 +80,41 @@ impl ClientInstance {
         };
 
         if response_ptr.is_null() {
-            return Some(ServiceResponse {
-                success: false,
-                server_id: ServiceId {
-                    service_id: unsafe { std::mem::zeroed() },
-                },
-                error_msg: Some("call failed".into()),
-                payload: vec![],
-            });
+            return Err(CallError::Failed(if self.is_connected() {
+                "no response from server".into()
+            } else {
+                "server is no longer connected".into()
+            }));
         }
 
-        unsafe {
-            let response = &*response_ptr;
-            let result = ServiceResponse::from_struct(response);
+        let result = unsafe {
+            let result = ServiceResponse::from_struct(&*response_ptr);
             eCAL_Free(response_ptr as *mut c_void);
-            Some(result)
+            result
+        };
+
+        if result.call_state == CallState::Timeout {
+            return Err(CallError::Timeout);
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the id of the server this instance is connected to.
+    pub fn get_client_id(&self) -> Option<EntityId> {
+        unsafe {
+            let raw = eCAL_ClientInstance_GetClientID(self.instance);
+            raw.as_ref().map(|id| EntityId::from(*id))
         }
     }
+
+    /// Returns `true` if the server of this instance is still connected.
+    pub fn is_connected(&self) -> bool {
+        unsafe { eCAL_ClientInstance_IsConnected(self.instance) != 0 }
+    }
 }
+
+// SAFETY: the eCAL C client instance is internally synchronized and the
+// owning list is shared through an `Arc`.
+unsafe impl Send for ClientInstance {}
+unsafe impl Sync for ClientInstance {}
@@ -4,25 +4,58 @@
 //!
 //! ## Functionality
 //! - `ServiceClient`: send requests to one or many services.
+//! - Client connection events and server availability checks.
+//! - Call strategies selecting one of several redundant servers.
+//! - Retry policies with exponential backoff for transient failures.
 //! - `ServiceServer`: host services, handle requests with callbacks.
+//! - Async method handlers on a tokio runtime (`async` feature).
+//! - Streaming calls delivering results in chunks over pub/sub (`streaming` feature).
+//!
+//! ## Thread Safety
+//! `ServiceServer`, `ServiceClient` and `ClientInstance` are `Send + Sync`,
+//! as eCAL synchronizes calls and callback registration internally. A client
+//! can be shared through an `Arc` and called from several threads at once.
+//!
+//! Method and event callbacks run on eCAL worker threads and must therefore
+//! be `Send + Sync`. Server methods can be added or removed at any time,
+//! including from within a running method callback.
 //!
 //! ## Example
-//! ```rust
-//! use rustecal_service::ServiceClient;
+//! ```no_run
+//! use rustecal_service::{ServiceClient, ServiceRequest};
 //! let client = ServiceClient::new("mirror_service").unwrap();
-//! let response = client.call("Hello!".as_bytes(), std::time::Duration::from_millis(500));
+//! let request = ServiceRequest { payload: b"Hello!".to_vec() };
+//! let responses = client.call("mirror", request, Some(500));
 //! ```
 
 pub mod types;
+pub mod call_strategy;
 pub mod client;
+pub mod client_event;
 pub mod client_instance;
 pub mod server;
 pub mod response;
+pub mod retry;
+#[cfg(feature = "async")]
+pub mod async_server;
+#[cfg(feature = "streaming")]
+pub mod streaming;
 
 // Public API
+pub use call_strategy::CallStrategy;
+pub use retry::{RetryOn, RetryPolicy};
 pub use client::ServiceClient;
+pub use client_event::{ClientEvent, ClientEventKind};
 pub use client_instance::ClientInstance;
 pub use server::ServiceServer;
 pub use types::ServiceRequest;
-pub use types::ServiceResponse;
+pub use types::ServiceError;
+pub use types::{CallError, CallState};
+pub use types::ClientMethod;
+#[cfg(feature = "async")]
+pub use async_server::AsyncMethodOptions;
+#[cfg(feature = "streaming")]
+pub use streaming::{ResponseStream, StreamCancelled, StreamError, StreamOptions, StreamSink};
+pub use types::ServiceId;
+pub use response::ServiceResponse;
 
@@ -6,7 +6,11 @@ use std::ffi::CStr;
 /// primarily used by clients to parse returned data.
 #[derive(Debug, Clone)]
 pub struct ServiceResponse {
-    pub success: bool,
+    /// How the call went on the transport level.
+    pub call_state: CallState,
+    /// Return state of the server's method callback; `0` means success.
+    pub ret_state: i32,
+    /// The responding server, including its host and process id.
     pub server_id: ServiceId,
     pub error_msg: Option<String>,
     pub payload: Vec<u8>,
@@ -14,8 +18,19 @@ pub struct ServiceResponse {
 
 impl ServiceResponse {
     /// Parses a raw FFI struct into a safe Rust response object.
+    ///
+    /// A call that was executed but returned a non-zero return state is
+    /// reported as [`CallState::Failed`]; if eCAL provides no error message,
+    /// the response payload (the message of a
+    /// [`ServiceError`](crate::types::ServiceError)) is used instead.
     pub fn from_struct(response: &eCAL_SServiceResponse) -> Self {
-        let success = CallState::from(response.call_state).is_success();
+        let executed = CallState::from(response.call_state).is_success();
+        let success = executed && response.ret_state == 0;
+        let call_state = if executed && !success {
+            CallState::Failed
+        } else {
+            CallState::from(response.call_state)
+        };
 
         let server_id = unsafe { ServiceId::from_ffi(&response.server_id) };
 
@@ -39,11 +54,23 @@ impl ServiceResponse {
             }
         };
 
+        let error_msg = if executed && !success && error_msg.as_deref().unwrap_or("").is_empty() {
+            Some(String::from_utf8_lossy(&payload).into_owned())
+        } else {
+            error_msg
+        };
+
         Self {
-            success,
+            call_state,
+            ret_state: response.ret_state,
             server_id,
             error_msg,
             payload,
         }
     }
+
+    /// Returns `true` if the call was executed and the method callback succeeded.
+    pub fn is_success(&self) -> bool {
+        self.call_state.is_success() && self.ret_state == 0
+    }
 }
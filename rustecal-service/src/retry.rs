@@ -0,0 +1,477 @@
+//! Retrying service calls that fail transiently, e.g. while servers restart.
+//!
+//! A [`RetryPolicy`] is applied by [`ServiceClient::call_with_retry`] and
+//! [`ClientInstance::call_with_retry`]. Async callers can use
+//! [`RetryPolicy::should_retry`] and [`RetryPolicy::backoff`] to drive the same
+//! policy with their runtime's sleep.
+
+use crate::client::ServiceClient;
+use crate::client_instance::ClientInstance;
+use crate::response::ServiceResponse;
+use crate::types::{CallError, CallState, ServiceRequest};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Outcomes of a call attempt that can be retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryOn {
+    /// No server answered in time ([`CallError::Timeout`] or [`CallState::Timeout`]).
+    Timeout,
+    /// No server was connected ([`CallError::NoServer`]).
+    NoServer,
+    /// The call failed on the transport level ([`CallError::Failed`], or
+    /// [`CallState::Failed`] with a zero return state).
+    Failed,
+    /// The method callback returned an error ([`CallState::Failed`] with a
+    /// non-zero return state).
+    MethodError,
+}
+
+/// When and how often a failed call is repeated.
+///
+/// The delay before attempt `n + 1` is `initial_backoff * multiplier^(n - 1)`,
+/// capped at `max_backoff` and randomized by `jitter`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between attempts.
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after every attempt.
+    pub multiplier: f64,
+    /// Relative random deviation of each delay, between `0.0` and `1.0`.
+    pub jitter: f64,
+    /// Overall time limit across all attempts. Per-attempt timeouts are
+    /// shortened to the remaining time. `None` means no limit.
+    pub deadline: Option<Duration>,
+    /// Outcomes that trigger a retry.
+    pub retry_on: Vec<RetryOn>,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 100 ms initial backoff doubling up to 2 s with 20 %
+    /// jitter, retrying timeouts and missing servers.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            multiplier: 2.0,
+            jitter: 0.2,
+            deadline: None,
+            retry_on: vec![RetryOn::Timeout, RetryOn::NoServer],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the total number of attempts.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the initial and maximum backoff.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets the backoff multiplier.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the jitter, clamped to `0.0..=1.0`.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the overall deadline.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the outcomes that trigger a retry.
+    pub fn with_retry_on(mut self, retry_on: &[RetryOn]) -> Self {
+        self.retry_on = retry_on.to_vec();
+        self
+    }
+
+    /// Returns the delay to wait after the given (1-based) failed attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(exponent);
+        let base = base.min(self.max_backoff.as_secs_f64());
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = 1.0 + jitter * (2.0 * random_unit() - 1.0);
+
+        Duration::from_secs_f64((base * factor).max(0.0))
+    }
+
+    /// Returns `true` if an attempt that produced `error` should be retried.
+    pub fn should_retry_error(&self, error: &CallError) -> bool {
+        let outcome = match error {
+            CallError::Timeout => RetryOn::Timeout,
+            CallError::NoServer(_) => RetryOn::NoServer,
+            CallError::Failed(_) => RetryOn::Failed,
+            CallError::InvalidMethod(_) => return false,
+        };
+        self.retry_on.contains(&outcome)
+    }
+
+    /// Returns `true` if an attempt that produced `response` should be retried.
+    pub fn should_retry_response(&self, response: &ServiceResponse) -> bool {
+        let outcome = match response.call_state {
+            CallState::Executed if response.ret_state == 0 => return false,
+            CallState::Executed => RetryOn::MethodError,
+            // executed calls whose method callback returned an error
+            CallState::Failed if response.ret_state != 0 => RetryOn::MethodError,
+            CallState::Timeout => RetryOn::Timeout,
+            _ => RetryOn::Failed,
+        };
+        self.retry_on.contains(&outcome)
+    }
+
+    /// Returns `true` if an attempt with the given result should be retried.
+    pub fn should_retry(&self, result: &Result<Vec<ServiceResponse>, CallError>) -> bool {
+        match result {
+            Ok(responses) => {
+                !responses.is_empty() && responses.iter().all(|r| self.should_retry_response(r))
+            }
+            Err(error) => self.should_retry_error(error),
+        }
+    }
+
+    /// Runs `attempt` until it succeeds, is not retryable, or the policy is exhausted.
+    ///
+    /// `attempt` receives the timeout for this attempt, shortened to the
+    /// remaining deadline.
+    fn run<T>(
+        &self,
+        timeout_ms: Option<i32>,
+        mut attempt: impl FnMut(Option<i32>) -> Result<T, CallError>,
+        retry_ok: impl Fn(&T) -> bool,
+    ) -> Result<T, CallError> {
+        let deadline = self.deadline.map(|d| Instant::now() + d);
+        let mut number = 1;
+
+        loop {
+            let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            if remaining == Some(Duration::ZERO) {
+                return Err(CallError::Timeout);
+            }
+
+            let result = attempt(attempt_timeout(timeout_ms, remaining));
+            let retry = match &result {
+                Ok(value) => retry_ok(value),
+                Err(error) => self.should_retry_error(error),
+            };
+
+            if !retry || number >= self.max_attempts {
+                return result;
+            }
+
+            let mut delay = self.backoff(number);
+            if let Some(deadline) = deadline {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if delay >= left {
+                    return result;
+                }
+                delay = delay.min(left);
+            }
+
+            std::thread::sleep(delay);
+            number += 1;
+        }
+    }
+}
+
+impl ServiceClient {
+    /// Calls `method` on all servers like [`ServiceClient::call`], retrying
+    /// according to `policy`.
+    ///
+    /// An attempt with responses is retried only if every response is retryable.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustecal_service::{ServiceClient, ServiceRequest, RetryPolicy};
+    /// use std::time::Duration;
+    ///
+    /// let client = ServiceClient::new("mirror").unwrap();
+    /// let policy = RetryPolicy::default().with_max_attempts(5).with_deadline(Duration::from_secs(3));
+    /// let request = ServiceRequest { payload: b"stressed".to_vec() };
+    /// let responses = client.call_with_retry("reverse", request, Some(500), &policy);
+    /// ```
+    pub fn call_with_retry(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+        policy: &RetryPolicy,
+    ) -> Result<Vec<ServiceResponse>, CallError> {
+        policy.run(
+            timeout_ms,
+            |timeout| self.call(method, request.clone(), timeout),
+            |responses| responses.iter().all(|r| policy.should_retry_response(r)),
+        )
+    }
+}
+
+impl ClientInstance {
+    /// Calls `method` on this server like [`ClientInstance::call`], retrying
+    /// according to `policy`.
+    pub fn call_with_retry(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+        policy: &RetryPolicy,
+    ) -> Result<ServiceResponse, CallError> {
+        policy.run(
+            timeout_ms,
+            |timeout| self.call(method, request.clone(), timeout),
+            |response| policy.should_retry_response(response),
+        )
+    }
+}
+
+/// Returns the timeout of an attempt, shortened to the remaining time.
+///
+/// A negative `timeout_ms` means no timeout, so the remaining time applies.
+fn attempt_timeout(timeout_ms: Option<i32>, remaining: Option<Duration>) -> Option<i32> {
+    match (timeout_ms, remaining) {
+        (Some(t), Some(r)) if t >= 0 => Some(t.min(duration_to_ms(r))),
+        (_, Some(r)) => Some(duration_to_ms(r)),
+        (t, None) => t,
+    }
+}
+
+/// Converts a remaining time to milliseconds, rounded up so that a remaining
+/// time under 1 ms does not become a zero timeout.
+fn duration_to_ms(duration: Duration) -> i32 {
+    duration.as_nanos().div_ceil(1_000_000).clamp(1, i32::MAX as u128) as i32
+}
+
+/// Returns a pseudo-random value.
+pub(crate) fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    hasher.write_u128(nanos);
+    hasher.finish()
+}
+
+/// Returns a pseudo-random value in `0.0..1.0`.
+fn random_unit() -> f64 {
+    (random_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ServiceId;
+    use rustecal_core::types::EntityId;
+    use std::cell::RefCell;
+
+    fn response(call_state: CallState, ret_state: i32) -> ServiceResponse {
+        ServiceResponse {
+            call_state,
+            ret_state,
+            server_id: ServiceId {
+                service_id: EntityId {
+                    entity_id: 1,
+                    process_id: 1,
+                    host_name: "host".into(),
+                },
+                service_name: "service".into(),
+            },
+            error_msg: None,
+            payload: vec![],
+        }
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy::default()
+            .with_backoff(Duration::from_millis(1), Duration::from_millis(1))
+            .with_jitter(0.0)
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(350))
+            .with_jitter(0.0);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn backoff_jitter_stays_in_bounds() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(0.5);
+
+        for _ in 0..100 {
+            let delay = policy.backoff(1);
+            assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(150));
+        }
+    }
+
+    #[test]
+    fn attempt_timeout_is_shortened_to_the_remaining_time() {
+        let remaining = Some(Duration::from_millis(300));
+
+        assert_eq!(attempt_timeout(Some(500), remaining), Some(300));
+        assert_eq!(attempt_timeout(Some(100), remaining), Some(100));
+        assert_eq!(attempt_timeout(None, remaining), Some(300));
+        assert_eq!(attempt_timeout(Some(500), None), Some(500));
+        assert_eq!(attempt_timeout(None, None), None);
+    }
+
+    #[test]
+    fn negative_timeout_uses_the_remaining_time() {
+        assert_eq!(attempt_timeout(Some(-1), Some(Duration::from_millis(300))), Some(300));
+        assert_eq!(attempt_timeout(Some(-1), None), Some(-1));
+    }
+
+    #[test]
+    fn remaining_time_is_rounded_up() {
+        assert_eq!(attempt_timeout(None, Some(Duration::from_micros(10))), Some(1));
+        assert_eq!(attempt_timeout(Some(500), Some(Duration::from_micros(1_500))), Some(2));
+        assert_eq!(attempt_timeout(None, Some(Duration::MAX)), Some(i32::MAX));
+    }
+
+    #[test]
+    fn errors_are_retried_per_retry_on() {
+        let policy = RetryPolicy::default();
+
+        assert!(policy.should_retry_error(&CallError::Timeout));
+        assert!(policy.should_retry_error(&CallError::NoServer("mirror".into())));
+        assert!(!policy.should_retry_error(&CallError::Failed("mirror".into())));
+        assert!(!policy.should_retry_error(&CallError::InvalidMethod("mirror".into())));
+
+        let policy = policy.with_retry_on(&[RetryOn::Failed]);
+        assert!(!policy.should_retry_error(&CallError::Timeout));
+        assert!(policy.should_retry_error(&CallError::Failed("mirror".into())));
+    }
+
+    #[test]
+    fn responses_are_retried_per_retry_on() {
+        let policy = RetryPolicy::default().with_retry_on(&[RetryOn::Timeout, RetryOn::MethodError]);
+
+        assert!(!policy.should_retry_response(&response(CallState::Executed, 0)));
+        assert!(policy.should_retry_response(&response(CallState::Failed, 3)));
+        assert!(policy.should_retry_response(&response(CallState::Timeout, 0)));
+        assert!(!policy.should_retry_response(&response(CallState::Failed, 0)));
+
+        assert!(!policy.should_retry(&Ok(vec![])));
+        assert!(!policy.should_retry(&Ok(vec![
+            response(CallState::Timeout, 0),
+            response(CallState::Executed, 0),
+        ])));
+        assert!(policy.should_retry(&Ok(vec![
+            response(CallState::Timeout, 0),
+            response(CallState::Failed, 1),
+        ])));
+    }
+
+    #[test]
+    fn run_stops_after_max_attempts() {
+        let policy = fast_policy().with_max_attempts(3);
+        let attempts = RefCell::new(0);
+
+        let result: Result<(), _> = policy.run(
+            Some(100),
+            |_| {
+                *attempts.borrow_mut() += 1;
+                Err(CallError::Timeout)
+            },
+            |_| false,
+        );
+
+        assert!(matches!(result, Err(CallError::Timeout)));
+        assert_eq!(*attempts.borrow(), 3);
+    }
+
+    #[test]
+    fn run_stops_on_success_and_non_retryable_errors() {
+        let policy = fast_policy().with_max_attempts(5);
+        let attempts = RefCell::new(0);
+
+        let result = policy.run(
+            None,
+            |_| {
+                *attempts.borrow_mut() += 1;
+                if *attempts.borrow() < 2 {
+                    Err(CallError::NoServer("mirror".into()))
+                } else {
+                    Ok(*attempts.borrow())
+                }
+            },
+            |_| false,
+        );
+        assert_eq!(result.unwrap(), 2);
+
+        *attempts.borrow_mut() = 0;
+        let result: Result<(), _> = policy.run(
+            None,
+            |_| {
+                *attempts.borrow_mut() += 1;
+                Err(CallError::InvalidMethod("mirror".into()))
+            },
+            |_| false,
+        );
+        assert!(matches!(result, Err(CallError::InvalidMethod(_))));
+        assert_eq!(*attempts.borrow(), 1);
+    }
+
+    #[test]
+    fn run_respects_the_deadline() {
+        let policy = RetryPolicy::default()
+            .with_max_attempts(u32::MAX)
+            .with_backoff(Duration::from_millis(20), Duration::from_millis(20))
+            .with_jitter(0.0)
+            .with_deadline(Duration::from_millis(100));
+        let timeouts = RefCell::new(Vec::new());
+
+        let started = Instant::now();
+        let result: Result<(), _> = policy.run(
+            Some(-1),
+            |timeout| {
+                timeouts.borrow_mut().push(timeout);
+                Err(CallError::Timeout)
+            },
+            |_| false,
+        );
+
+        assert!(matches!(result, Err(CallError::Timeout)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        let timeouts = timeouts.borrow();
+        assert!(timeouts.len() > 1);
+        assert!(timeouts.iter().all(|t| matches!(t, Some(1..=100))));
+    }
+}
@@ -1,24 +1,61 @@
 use rustecal_sys::*;
-use crate::types::{MethodInfo, ServiceCallback};
+use rustecal_core::EcalGuard;
+use crate::client::ServiceClient;
+use crate::types::{MethodInfo, ServiceCallback, ServiceError, ServiceId};
+use rustecal_core::types::DataTypeInfo;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_int, c_void};
 use std::ptr;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, RwLock};
 
-type SharedCallback = Arc<Mutex<HashMap<String, ServiceCallback>>>;
+/// A registered method callback, shared so it can be invoked without holding a lock.
+type MethodCallback =
+    Arc<dyn Fn(MethodInfo, &[u8]) -> Result<Vec<u8>, ServiceError> + Send + Sync + 'static>;
+
+/// Method callbacks and the optional fallback handler of a server.
+///
+/// Locks are only held to look up or swap a callback, never while a callback
+/// runs, so methods execute concurrently and handlers may add or remove
+/// methods themselves.
+#[derive(Default)]
+struct Callbacks {
+    methods: RwLock<HashMap<String, MethodCallback>>,
+    fallback: RwLock<Option<MethodCallback>>,
+}
+
+impl Callbacks {
+    /// Returns the callback for `method`, or the fallback handler if none is registered.
+    fn lookup(&self, method: &str) -> Option<MethodCallback> {
+        let methods = self.methods.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(cb) = methods.get(method) {
+            return Some(cb.clone());
+        }
+        drop(methods);
+
+        self.fallback.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+type SharedCallback = Arc<Callbacks>;
 
 /// Represents a service server that can handle RPC-style requests.
+///
+/// # Thread Safety
+///
+/// `ServiceServer` is `Send + Sync`, see the [crate documentation](crate#thread-safety).
 pub struct ServiceServer {
     handle: *mut eCAL_ServiceServer,
     callbacks: SharedCallback,
+    /// Keeps a guarded eCAL runtime alive while this handle exists.
+    _guard: Option<EcalGuard>,
 }
 
 impl ServiceServer {
     pub fn new(service_name: &str) -> Result<Self, String> {
         let c_service_name = CString::new(service_name).map_err(|_| "Invalid service name")?;
 
-        let callbacks: SharedCallback = Arc::new(Mutex::new(HashMap::new()));
+        let callbacks: SharedCallback = Arc::new(Callbacks::default());
         let handle = unsafe { eCAL_ServiceServer_New(c_service_name.as_ptr(), None) };
         if handle.is_null() {
             return Err("Failed to create eCAL_ServiceServer".into());
@@ -27,36 +64,141 @@ impl ServiceServer {
         Ok(Self {
             handle,
             callbacks,
+            _guard: EcalGuard::current(),
         })
     }
 
-    pub fn add_method(&mut self, method: &str, callback: ServiceCallback) -> Result<(), String> {
+    /// Returns the id of this server, as seen by clients in their responses.
+    pub fn get_service_id(&self) -> Option<ServiceId> {
+        unsafe {
+            let raw = eCAL_ServiceServer_GetServiceId(self.handle);
+            raw.as_ref().map(|id| ServiceId::from_ffi(id))
+        }
+    }
+
+    /// Registers a method callback under the given name.
+    ///
+    /// The callback returns the response payload, or a [`ServiceError`] that
+    /// the client receives as a failed call with the error message.
+    /// Registering an existing method name replaces its callback.
+    pub fn add_method(&self, method: &str, callback: ServiceCallback) -> Result<(), String> {
         let c_method = CString::new(method).map_err(|_| "Invalid method name")?;
 
         let mut method_info: eCAL_SServiceMethodInformation = unsafe { std::mem::zeroed() };
         method_info.method_name = c_method.as_ptr();
 
+        self.register_method(method, &method_info, callback)
+    }
+
+    /// Registers a method callback and announces its request and response types.
+    ///
+    /// The types show up in `ServerInfo.methods` of the monitoring layer and let
+    /// clients in other languages decode the payloads, e.g. protobuf services
+    /// called from C++.
+    pub fn add_typed_method(
+        &self,
+        method: &str,
+        request_type: &DataTypeInfo,
+        response_type: &DataTypeInfo,
+        callback: ServiceCallback,
+    ) -> Result<(), String> {
+        let c_method = CString::new(method).map_err(|_| "Invalid method name")?;
+
+        // The C strings must outlive the registration call
+        let mut c_strings = Vec::with_capacity(4);
+        for datatype in [request_type, response_type] {
+            c_strings.push(CString::new(datatype.type_name.as_str()).map_err(|_| "Invalid type name")?);
+            c_strings.push(CString::new(datatype.encoding.as_str()).map_err(|_| "Invalid encoding string")?);
+        }
+
+        let method_info = eCAL_SServiceMethodInformation {
+            method_name: c_method.as_ptr(),
+            request_type: ServiceClient::raw_datatype(&c_strings[0], &c_strings[1], &request_type.descriptor),
+            response_type: ServiceClient::raw_datatype(&c_strings[2], &c_strings[3], &response_type.descriptor),
+        };
+
+        self.register_method(method, &method_info, callback)
+    }
+
+    fn register_method(
+        &self,
+        method: &str,
+        method_info: &eCAL_SServiceMethodInformation,
+        callback: ServiceCallback,
+    ) -> Result<(), String> {
+        // Store the callback first, so a call arriving right after
+        // registration already finds it.
         self.callbacks
-            .lock()
-            .unwrap()
-            .insert(method.to_string(), callback);
+            .methods
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(method.to_string(), Arc::from(callback));
 
         let result = unsafe {
             eCAL_ServiceServer_SetMethodCallback(
                 self.handle,
-                &method_info,
+                method_info,
                 Some(Self::dispatch),
                 Arc::as_ptr(&self.callbacks) as *mut c_void,
             )
         };
 
         if result != 0 {
+            self.callbacks
+                .methods
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(method);
             Err("Failed to register method callback".into())
         } else {
             Ok(())
         }
     }
 
+    /// Unregisters a method from the server.
+    ///
+    /// Calls that are already running finish normally; later calls to this
+    /// method are rejected by eCAL.
+    pub fn remove_method(&self, method: &str) -> Result<(), String> {
+        let c_method = CString::new(method).map_err(|_| "Invalid method name")?;
+
+        let result = unsafe { eCAL_ServiceServer_RemoveMethodCallback(self.handle, c_method.as_ptr()) };
+
+        let removed = self
+            .callbacks
+            .methods
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(method);
+
+        if result != 0 || removed.is_none() {
+            Err(format!("Method '{}' is not registered", method))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sets a handler for calls whose method name matches no registered callback.
+    ///
+    /// The handler receives the called method name in [`MethodInfo`] and
+    /// replaces a previously set fallback. Without a fallback, such calls fail
+    /// with an "unknown method" error.
+    ///
+    /// eCAL only forwards calls to method names that were registered with the
+    /// server, so calls to names that were never added are rejected by eCAL
+    /// before they reach the fallback. It does see calls to methods that are
+    /// being removed with [`ServiceServer::remove_method`] while the call is
+    /// already on its way.
+    pub fn set_fallback_method(&self, callback: ServiceCallback) {
+        *self.callbacks.fallback.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::from(callback));
+    }
+
+    /// Removes the fallback handler set with [`ServiceServer::set_fallback_method`].
+    pub fn remove_fallback_method(&self) {
+        let old = self.callbacks.fallback.write().unwrap_or_else(|e| e.into_inner()).take();
+        drop(old);
+    }
+
     unsafe extern "C" fn dispatch(
         method_info: *const eCAL_SServiceMethodInformation,
         request_ptr: *const c_void,
@@ -65,10 +207,10 @@ impl ServiceServer {
         response_len: *mut usize,
         user_data: *mut c_void,
     ) -> c_int {
-        let callbacks = {
-            let raw = user_data as *const Mutex<HashMap<String, ServiceCallback>>;
-            unsafe { &*raw }.lock().unwrap()
-        };
+        if user_data.is_null() {
+            return 1;
+        }
+        let callbacks = unsafe { &*(user_data as *const Callbacks) };
 
         let method_name = {
             if method_info.is_null() || unsafe { (*method_info).method_name }.is_null() {
@@ -94,12 +236,29 @@ impl ServiceServer {
             response_type: None,
         };
 
-        let cb = match callbacks.get(&method_name) {
-            Some(cb) => cb,
-            None => return 1,
+        // No lock is held while the callback runs; names without a callback
+        // go to the fallback handler, if any
+        let result = match callbacks.lookup(&method_name) {
+            Some(cb) => cb(info, request),
+            None => Err(ServiceError::new(format!(
+                "service has no method named '{}'",
+                method_name
+            ))),
         };
 
-        let response = cb(info, request);
+        // Errors are transported as non-zero return state with the message as payload
+        let (ret_state, response) = match result {
+            Ok(response) => (0, response),
+            Err(err) => (err.code(), err.message.into_bytes()),
+        };
+
+        if response.is_empty() {
+            unsafe {
+                *response_ptr = ptr::null_mut();
+                *response_len = 0;
+            }
+            return ret_state;
+        }
 
         let buffer = unsafe { eCAL_Malloc(response.len()) };
         if buffer.is_null() {
@@ -112,16 +271,21 @@ impl ServiceServer {
             *response_len = response.len();
         }
 
-        0
+        ret_state
     }
 }
 
+// SAFETY: the eCAL C server is internally synchronized and the callback map
+// is protected by locks.
+unsafe impl Send for ServiceServer {}
+unsafe impl Sync for ServiceServer {}
+
 impl Drop for ServiceServer {
+    /// Deletes the eCAL server; the callbacks are released afterwards when
+    /// the last reference to them is dropped.
     fn drop(&mut self) {
         unsafe {
             eCAL_ServiceServer_Delete(self.handle);
-            let ptr = Arc::as_ptr(&self.callbacks) as *const _;
-            let _ = Arc::from_raw(ptr);
         }
     }
 }
@@ -0,0 +1,455 @@
+//! Streaming calls that deliver a result in many chunks (requires the `streaming` feature).
+//!
+//! A streaming call is a regular service call that returns a stream token.
+//! The token names a private topic on which the server publishes the result
+//! chunks:
+//!
+//! 1. The client calls the method on one server and receives the token.
+//! 2. The client subscribes to the token topic.
+//! 3. The server waits for the subscription, runs the handler and publishes
+//!    every chunk with a sequence number, followed by an end marker.
+//!
+//! [`ResponseStream`] delivers the chunks in order and reports lost chunks,
+//! server errors and idle timeouts. Dropping or cancelling the stream stops
+//! the handler on the server.
+
+use crate::call_strategy::CallStrategy;
+use crate::client::ServiceClient;
+use crate::server::ServiceServer;
+use crate::types::{CallError, MethodInfo, ServiceError, ServiceId, ServiceRequest};
+use rustecal_core::types::{DataTypeInfo, EcalType};
+use rustecal_core::Process;
+use rustecal_pubsub::typed_subscriber::Received;
+use rustecal_pubsub::{PublisherMessage, SubscriberMessage, TypedPublisher, TypedSubscriber};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Prefix of all stream topics.
+const STREAM_TOPIC_PREFIX: &str = "__rustecal_stream/";
+/// Method every streaming server offers to cancel a running stream.
+const CANCEL_METHOD: &str = "__rustecal_stream_cancel";
+
+const FRAME_CHUNK: u8 = 0;
+const FRAME_END: u8 = 1;
+const FRAME_ERROR: u8 = 2;
+
+/// Cancellation flags of the streams running in this process, by token.
+static ACTIVE_STREAMS: Mutex<Option<HashMap<String, Arc<AtomicBool>>>> = Mutex::new(None);
+static NEXT_STREAM: AtomicU64 = AtomicU64::new(1);
+/// Chunks a [`ResponseStream`] buffers ahead of a missing one before failing.
+const MAX_PENDING_CHUNKS: usize = 1024;
+
+/// A frame on a stream topic: kind, sequence number and payload.
+struct StreamFrame {
+    kind: u8,
+    seq: u64,
+    payload: Vec<u8>,
+}
+
+impl EcalType for StreamFrame {
+    fn datatype() -> DataTypeInfo {
+        DataTypeInfo {
+            type_name: "rustecal::StreamFrame".into(),
+            encoding: "rustecal-stream".into(),
+            descriptor: vec![],
+        }
+    }
+}
+
+impl PublisherMessage for StreamFrame {
+    fn to_bytes(&self) -> Arc<[u8]> {
+        let mut bytes = Vec::with_capacity(9 + self.payload.len());
+        bytes.push(self.kind);
+        bytes.extend_from_slice(&self.seq.to_le_bytes());
+        bytes.extend_from_slice(&self.payload);
+        Arc::from(bytes)
+    }
+}
+
+impl SubscriberMessage for StreamFrame {
+    fn from_bytes(bytes: Arc<[u8]>, _data_type_info: &DataTypeInfo) -> Option<Self> {
+        if bytes.len() < 9 {
+            return None;
+        }
+        let seq = u64::from_le_bytes(bytes[1..9].try_into().ok()?);
+        Some(Self {
+            kind: bytes[0],
+            seq,
+            payload: bytes[9..].to_vec(),
+        })
+    }
+}
+
+fn set_stream_flag(token: &str, flag: Option<Arc<AtomicBool>>) {
+    let mut streams = ACTIVE_STREAMS.lock().unwrap_or_else(|e| e.into_inner());
+    let streams = streams.get_or_insert_with(HashMap::new);
+    match flag {
+        Some(flag) => streams.insert(token.to_string(), flag),
+        None => streams.remove(token),
+    };
+}
+
+/// Settings of a streaming method.
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// How long the server waits for the client to subscribe to the stream
+    /// before giving up.
+    pub subscribe_timeout: Duration,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            subscribe_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Returned by [`StreamSink::send`] once the client cancelled the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("stream was cancelled by the client")]
+pub struct StreamCancelled;
+
+/// The sending side of a stream, passed to streaming method handlers.
+pub struct StreamSink {
+    publisher: TypedPublisher<StreamFrame>,
+    cancelled: Arc<AtomicBool>,
+    seq: u64,
+}
+
+impl StreamSink {
+    /// Publishes the next chunk.
+    ///
+    /// Fails once the client cancelled the stream or went away; the handler
+    /// should return as soon as possible then.
+    pub fn send(&mut self, chunk: &[u8]) -> Result<(), StreamCancelled> {
+        if self.is_cancelled() {
+            return Err(StreamCancelled);
+        }
+
+        self.publish(FRAME_CHUNK, chunk.to_vec());
+        self.seq += 1;
+        Ok(())
+    }
+
+    /// Returns `true` if the client cancelled the stream or unsubscribed.
+    pub fn is_cancelled(&self) -> bool {
+        if self.publisher.get_subscriber_count() == 0 {
+            self.cancelled.store(true, Ordering::SeqCst);
+        }
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of chunks sent so far.
+    pub fn sent(&self) -> u64 {
+        self.seq
+    }
+
+    fn publish(&self, kind: u8, payload: Vec<u8>) {
+        self.publisher.send(&StreamFrame {
+            kind,
+            seq: self.seq,
+            payload,
+        });
+    }
+}
+
+impl ServiceServer {
+    /// Registers a streaming method.
+    ///
+    /// Every call runs `callback` on a separate thread, which sends the
+    /// result chunks through the [`StreamSink`]. Returning `Ok` completes the
+    /// stream; returning a [`ServiceError`] ends it with that error on the
+    /// client side.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustecal_service::{ServiceServer, StreamOptions};
+    ///
+    /// let server = ServiceServer::new("numbers").unwrap();
+    /// server.add_streaming_method("count", StreamOptions::default(), |_info, _request, sink| {
+    ///     for i in 0u32..1000 {
+    ///         if sink.send(&i.to_le_bytes()).is_err() {
+    ///             break;
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }).unwrap();
+    /// ```
+    pub fn add_streaming_method<F>(
+        &self,
+        method: &str,
+        options: StreamOptions,
+        callback: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(MethodInfo, Vec<u8>, &mut StreamSink) -> Result<(), ServiceError> + Send + Sync + 'static,
+    {
+        self.add_method(
+            CANCEL_METHOD,
+            Box::new(|_info, request: &[u8]| {
+                let token = String::from_utf8_lossy(request);
+                let streams = ACTIVE_STREAMS.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(flag) = streams.as_ref().and_then(|s| s.get(token.as_ref())) {
+                    flag.store(true, Ordering::SeqCst);
+                }
+                Ok(Vec::new())
+            }),
+        )?;
+
+        let callback = Arc::new(callback);
+
+        self.add_method(
+            method,
+            Box::new(move |info: MethodInfo, request: &[u8]| {
+                let token = format!(
+                    "{}{}/{}/{}",
+                    STREAM_TOPIC_PREFIX,
+                    Process::get_host_name(),
+                    Process::get_process_id(),
+                    NEXT_STREAM.fetch_add(1, Ordering::Relaxed)
+                );
+
+                let publisher = TypedPublisher::<StreamFrame>::new(&token).map_err(ServiceError::new)?;
+                let cancelled = Arc::new(AtomicBool::new(false));
+                set_stream_flag(&token, Some(cancelled.clone()));
+
+                let callback = callback.clone();
+                let request = request.to_vec();
+                let subscribe_timeout = options.subscribe_timeout;
+                let thread_token = token.clone();
+
+                std::thread::spawn(move || {
+                    // Chunks published before the client subscribed would be lost
+                    let deadline = Instant::now() + subscribe_timeout;
+                    while publisher.get_subscriber_count() == 0 {
+                        if Instant::now() >= deadline || cancelled.load(Ordering::SeqCst) {
+                            set_stream_flag(&thread_token, None);
+                            return;
+                        }
+                        std::thread::sleep(Duration::from_millis(5));
+                    }
+
+                    let mut sink = StreamSink {
+                        publisher,
+                        cancelled,
+                        seq: 0,
+                    };
+
+                    match callback(info, request, &mut sink) {
+                        Ok(()) => sink.publish(FRAME_END, Vec::new()),
+                        Err(err) => sink.publish(FRAME_ERROR, err.message.into_bytes()),
+                    }
+
+                    set_stream_flag(&thread_token, None);
+                });
+
+                Ok(token.into_bytes())
+            }),
+        )
+    }
+}
+
+/// Errors reported by a [`ResponseStream`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum StreamError {
+    /// The server handler returned an error.
+    #[error("stream failed on the server: {0}")]
+    Server(String),
+    /// No frame arrived within the idle timeout.
+    #[error("stream timed out")]
+    Timeout,
+    /// The chunk with this sequence number never arrived, before the end of
+    /// the stream or before too many later chunks were buffered.
+    #[error("stream chunk {0} was lost")]
+    MissingChunk(u64),
+}
+
+/// The receiving side of a streaming call.
+///
+/// Yields the chunks in the order the server sent them and ends after the
+/// last chunk, or after the first error. Dropping the stream before it has
+/// ended cancels it on the server.
+pub struct ResponseStream<'a> {
+    client: &'a ServiceClient,
+    server_id: ServiceId,
+    token: String,
+    _subscriber: TypedSubscriber<StreamFrame>,
+    frames: Receiver<StreamFrame>,
+    idle_timeout: Option<Duration>,
+    next_seq: u64,
+    pending: BTreeMap<u64, Vec<u8>>,
+    end_seq: Option<u64>,
+    finished: bool,
+}
+
+impl ResponseStream<'_> {
+    /// Returns the server producing this stream.
+    pub fn server_id(&self) -> &ServiceId {
+        &self.server_id
+    }
+
+    /// Sets how long to wait for the next frame before failing with
+    /// [`StreamError::Timeout`]. Defaults to the call timeout, or 5 seconds;
+    /// a negative call timeout waits forever.
+    pub fn set_idle_timeout(&mut self, timeout: Duration) {
+        self.idle_timeout = Some(timeout);
+    }
+
+    /// Stops the stream and asks the server to stop producing chunks.
+    pub fn cancel(&mut self) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+
+        let instance = self
+            .client
+            .get_client_instances()
+            .into_iter()
+            .find(|i| i.get_client_id().map(|id| id.entity_id) == Some(self.server_id.service_id.entity_id));
+
+        if let Some(instance) = instance {
+            let request = ServiceRequest {
+                payload: self.token.clone().into_bytes(),
+            };
+            let _ = instance.call(CANCEL_METHOD, request, Some(500));
+        }
+    }
+
+    /// Returns `true` once the stream has ended, failed or was cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+impl Iterator for ResponseStream<'_> {
+    type Item = Result<Vec<u8>, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            if let Some(chunk) = self.pending.remove(&self.next_seq) {
+                self.next_seq += 1;
+                return Some(Ok(chunk));
+            }
+
+            if self.end_seq == Some(self.next_seq) {
+                self.finished = true;
+                return None;
+            }
+
+            let frame = match self.idle_timeout {
+                Some(timeout) => self.frames.recv_timeout(timeout),
+                None => self.frames.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match frame {
+                Ok(frame) => match frame.kind {
+                    FRAME_CHUNK if frame.seq >= self.next_seq => {
+                        // bounded, so a lost chunk cannot buffer the rest of the stream
+                        if self.pending.len() >= MAX_PENDING_CHUNKS {
+                            self.cancel();
+                            return Some(Err(StreamError::MissingChunk(self.next_seq)));
+                        }
+                        self.pending.insert(frame.seq, frame.payload);
+                    }
+                    FRAME_END => self.end_seq = Some(frame.seq),
+                    FRAME_ERROR => {
+                        self.finished = true;
+                        return Some(Err(StreamError::Server(
+                            String::from_utf8_lossy(&frame.payload).into_owned(),
+                        )));
+                    }
+                    _ => {}
+                },
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                    let error = match self.end_seq {
+                        Some(_) => StreamError::MissingChunk(self.next_seq),
+                        None => StreamError::Timeout,
+                    };
+                    self.cancel();
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ResponseStream<'_> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+impl ServiceClient {
+    /// Starts a streaming call of `method` on one server selected by `strategy`.
+    ///
+    /// `timeout_ms` applies to the initial call and, by default, to the gap
+    /// between two chunks. A negative timeout waits forever.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use rustecal_service::{ServiceClient, ServiceRequest, CallStrategy};
+    ///
+    /// let client = ServiceClient::new("numbers").unwrap();
+    /// let request = ServiceRequest { payload: vec![] };
+    /// for chunk in client.call_streaming("count", request, Some(1000), &CallStrategy::RoundRobin).unwrap() {
+    ///     println!("{} bytes", chunk.unwrap().len());
+    /// }
+    /// ```
+    pub fn call_streaming(
+        &self,
+        method: &str,
+        request: ServiceRequest,
+        timeout_ms: Option<i32>,
+        strategy: &CallStrategy,
+    ) -> Result<ResponseStream<'_>, CallError> {
+        let response = self.call_with_strategy(method, request, timeout_ms, strategy)?;
+        if !response.is_success() {
+            return Err(CallError::Failed(
+                response.error_msg.unwrap_or_else(|| "streaming call failed".into()),
+            ));
+        }
+
+        let token = String::from_utf8(response.payload)
+            .ok()
+            .filter(|t| t.starts_with(STREAM_TOPIC_PREFIX))
+            .ok_or_else(|| CallError::Failed(format!("method '{}' is not a streaming method", method)))?;
+
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        let subscriber = TypedSubscriber::<StreamFrame>::new(&token).map_err(CallError::Failed)?;
+        subscriber.set_callback(move |frame: Received<StreamFrame>| {
+            let _ = tx.lock().unwrap_or_else(|e| e.into_inner()).send(frame.payload);
+        });
+
+        let idle_timeout = match timeout_ms {
+            Some(t) if t < 0 => None,
+            Some(t) => Some(Duration::from_millis(t as u64)),
+            None => Some(Duration::from_secs(5)),
+        };
+
+        Ok(ResponseStream {
+            client: self,
+            server_id: response.server_id,
+            token,
+            _subscriber: subscriber,
+            frames: rx,
+            idle_timeout,
+            next_seq: 0,
+            pending: BTreeMap::new(),
+            end_seq: None,
+            finished: false,
+        })
+    }
+}
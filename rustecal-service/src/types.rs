@@ -1,6 +1,9 @@
+use rustecal_core::types::{DataTypeInfo, EcalType, EntityId};
 use rustecal_sys::*;
+use std::ffi::CStr;
+use thiserror::Error;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CallState {
     None,
     Executed,
@@ -33,15 +36,34 @@ impl From<u32> for CallState {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Identifies a service server or client instance.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ServiceId {
-    pub service_id: eCAL_SEntityId,
+    /// The eCAL entity (id, process and host).
+    pub service_id: EntityId,
+    /// Name of the service.
+    pub service_name: String,
 }
 
 impl ServiceId {
+    /// Copies a raw eCAL service id into an owned value.
+    ///
+    /// # Safety
+    ///
+    /// The string pointers in `raw` must be null or point to valid
+    /// null-terminated strings.
     pub unsafe fn from_ffi(raw: &eCAL_SServiceId) -> Self {
+        let service_name = if raw.service_name.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(raw.service_name) }
+                .to_string_lossy()
+                .into_owned()
+        };
+
         Self {
-            service_id: raw.service_id,
+            service_id: EntityId::from(raw.service_id),
+            service_name,
         }
     }
 }
@@ -51,14 +73,6 @@ pub struct ServiceRequest {
     pub payload: Vec<u8>,
 }
 
-#[derive(Debug, Clone)]
-pub struct ServiceResponse {
-    pub success: bool,
-    pub server_id: ServiceId,
-    pub error_msg: Option<String>,
-    pub payload: Vec<u8>,
-}
-
 /// Metadata passed to method callbacks about the method interface.
 #[derive(Debug, Clone)]
 pub struct MethodInfo {
@@ -67,9 +81,142 @@ pub struct MethodInfo {
     pub response_type: Option<String>,
 }
 
+/// A method a [`ServiceClient`](crate::ServiceClient) declares to call,
+/// including its request and response types.
+///
+/// Declared methods are registered with eCAL and show up in
+/// `ClientInfo.methods` of the monitoring layer.
+#[derive(Debug, Clone)]
+pub struct ClientMethod {
+    pub method_name: String,
+    pub request_type: DataTypeInfo,
+    pub response_type: DataTypeInfo,
+}
+
+impl ClientMethod {
+    /// Declares a method without type information.
+    pub fn new(method_name: &str) -> Self {
+        Self::with_types(method_name, empty_datatype(), empty_datatype())
+    }
+
+    /// Declares a method with request and response types.
+    pub fn with_types(method_name: &str, request_type: DataTypeInfo, response_type: DataTypeInfo) -> Self {
+        Self {
+            method_name: method_name.to_string(),
+            request_type,
+            response_type,
+        }
+    }
+
+    /// Declares a method with the types of the request and response messages.
+    ///
+    /// ```
+    /// use rustecal_core::types::{DataTypeInfo, EcalType};
+    /// use rustecal_service::ClientMethod;
+    ///
+    /// struct Text;
+    ///
+    /// impl EcalType for Text {
+    ///     fn datatype() -> DataTypeInfo {
+    ///         DataTypeInfo { encoding: "utf-8".into(), type_name: "string".into(), descriptor: vec![] }
+    ///     }
+    /// }
+    ///
+    /// let method = ClientMethod::typed::<Text, Text>("echo");
+    /// assert_eq!(method.request_type.encoding, "utf-8");
+    /// ```
+    pub fn typed<Req: EcalType, Resp: EcalType>(method_name: &str) -> Self {
+        Self::with_types(
+            method_name,
+            Req::datatype_info().clone(),
+            Resp::datatype_info().clone(),
+        )
+    }
+}
+
+fn empty_datatype() -> DataTypeInfo {
+    DataTypeInfo {
+        type_name: String::new(),
+        encoding: String::new(),
+        descriptor: vec![],
+    }
+}
+
+/// An application error returned by a service method callback.
+///
+/// The error is sent back to the caller as a non-zero return state with the
+/// message as response payload. Rust clients report it as a failed call with
+/// `error_msg` set to the message.
+#[derive(Debug, Clone, Error)]
+#[error("{message} (code {code})")]
+pub struct ServiceError {
+    /// Return state reported to the client. Always non-zero, so it is only
+    /// set through the constructors.
+    code: i32,
+    /// Human-readable error description.
+    pub message: String,
+}
+
+impl ServiceError {
+    /// Default return state used by [`ServiceError::new`].
+    pub const DEFAULT_CODE: i32 = 1;
+
+    /// Creates an error with the default return state.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self::with_code(Self::DEFAULT_CODE, message)
+    }
+
+    /// Creates an error with a custom return state.
+    ///
+    /// A `code` of `0` would signal success to the client and is replaced
+    /// with [`ServiceError::DEFAULT_CODE`].
+    pub fn with_code(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code: if code == 0 { Self::DEFAULT_CODE } else { code },
+            message: message.into(),
+        }
+    }
+
+    /// Returns the return state reported to the client; never `0`.
+    pub fn code(&self) -> i32 {
+        self.code
+    }
+}
+
+impl From<String> for ServiceError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for ServiceError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+/// Reasons a client call produced no responses.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CallError {
+    /// The method name is not valid or was not declared by the client.
+    #[error("invalid method '{0}'")]
+    InvalidMethod(String),
+    /// No server of the service is connected.
+    #[error("no server available for service '{0}'")]
+    NoServer(String),
+    /// No server responded within the timeout.
+    #[error("service call timed out")]
+    Timeout,
+    /// The call failed before any response was received.
+    #[error("service call failed: {0}")]
+    Failed(String),
+}
+
 /// The service callback signature used by ServiceServer.
 ///
 /// Mimics the eCAL C++ API:
 /// - Accepts `MethodInfo` and a reference to request bytes
-/// - Returns response bytes (`Vec<u8>`)
-pub type ServiceCallback = Box<dyn Fn(MethodInfo, &[u8]) -> Vec<u8> + Send + Sync + 'static>;
+/// - Returns response bytes (`Vec<u8>`), or a [`ServiceError`] that is
+///   reported to the client as a failed call
+pub type ServiceCallback =
+    Box<dyn Fn(MethodInfo, &[u8]) -> Result<Vec<u8>, ServiceError> + Send + Sync + 'static>;
@@ -0,0 +1,82 @@
+//! Shares service clients and servers between threads while calls are running.
+//!
+//! These tests need a local eCAL installation, like the samples.
+
+use rustecal_core::{EcalComponents, EcalGuard};
+use rustecal_service::{ServiceClient, ServiceError, ServiceRequest, ServiceServer};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+const CALLERS: usize = 4;
+const CALLS_PER_CALLER: usize = 50;
+
+fn mirror(server: &ServiceServer, tag: &'static [u8]) {
+    server
+        .add_method(
+            "mirror",
+            Box::new(move |_, request: &[u8]| {
+                let mut response = tag.to_vec();
+                response.extend_from_slice(request);
+                Ok::<_, ServiceError>(response)
+            }),
+        )
+        .unwrap();
+}
+
+#[test]
+fn shared_client_and_server_across_threads() {
+    let _ecal = EcalGuard::new(Some("rustecal_service_thread_safety"), EcalComponents::DEFAULT).unwrap();
+
+    let server = Arc::new(ServiceServer::new("thread_safety_service").unwrap());
+    mirror(&server, b"a:");
+
+    let client = Arc::new(ServiceClient::new("thread_safety_service").unwrap());
+    assert!(client.wait_for_server(Duration::from_secs(5)), "server did not connect");
+
+    // one thread keeps replacing the method callback while calls are running
+    let running = Arc::new(AtomicBool::new(true));
+    let replacer = {
+        let server = server.clone();
+        let running = running.clone();
+        thread::spawn(move || {
+            let mut flip = false;
+            while running.load(Ordering::SeqCst) {
+                mirror(&server, if flip { b"a:" } else { b"b:" });
+                flip = !flip;
+            }
+        })
+    };
+
+    let unexpected = Arc::new(AtomicUsize::new(0));
+    let callers: Vec<_> = (0..CALLERS)
+        .map(|i| {
+            let client = client.clone();
+            let unexpected = unexpected.clone();
+            thread::spawn(move || {
+                let request = format!("caller {}", i).into_bytes();
+                for _ in 0..CALLS_PER_CALLER {
+                    let responses = client
+                        .call("mirror", ServiceRequest { payload: request.clone() }, Some(1000))
+                        .unwrap();
+                    for response in responses {
+                        let payload = &response.payload;
+                        let tagged = payload.starts_with(b"a:") || payload.starts_with(b"b:");
+                        if !response.is_success() || !tagged || payload[2..] != request[..] {
+                            unexpected.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for caller in callers {
+        caller.join().unwrap();
+    }
+    running.store(false, Ordering::SeqCst);
+    replacer.join().unwrap();
+
+    assert_eq!(unexpected.load(Ordering::SeqCst), 0);
+}
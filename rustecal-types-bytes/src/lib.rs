@@ -10,7 +10,7 @@
 //! ```
 
 use std::sync::Arc;
-use rustecal_core::types::DataTypeInfo;
+use rustecal_core::types::{DataTypeInfo, EcalType};
 use rustecal_pubsub::typed_publisher::PublisherMessage;
 use rustecal_pubsub::typed_subscriber::SubscriberMessage;
 
@@ -22,7 +22,7 @@ pub struct BytesMessage {
     pub data: Arc<[u8]>,
 }
 
-impl SubscriberMessage for BytesMessage {
+impl EcalType for BytesMessage {
     /// Returns metadata describing the message encoding and type.
     ///
     /// Encoding is `"raw"`, type name is `"bytes"`, and no descriptor is included.
@@ -33,7 +33,9 @@ impl SubscriberMessage for BytesMessage {
             descriptor: vec![],
         }
     }
+}
 
+impl SubscriberMessage for BytesMessage {
     /// Creates a `BytesMessage` from a raw byte slice.
     fn from_bytes(bytes: Arc<[u8]>, _data_type_info: &DataTypeInfo) -> Option<Self> {
         Some(BytesMessage { data: Arc::from(bytes) })
@@ -41,11 +43,6 @@ impl SubscriberMessage for BytesMessage {
 }
 
 impl PublisherMessage for BytesMessage {
-    /// Reuses the `SubscriberMessage::datatype()` implementation.
-    fn datatype() -> DataTypeInfo {
-        <BytesMessage as SubscriberMessage>::datatype()
-    }
-
     /// Returns the internal binary data as an Arc<[u8]> for zero-copy transmission.
     fn to_bytes(&self) -> Arc<[u8]> {
         self.data.clone()
@@ -5,6 +5,8 @@
 //! ## Features
 //! - Wraps `prost`-based Protobuf types for eCAL transport.
 //! - Static descriptor embedding via `include_bytes!`.
+//! - Optional `prost-build` service generator for typed eCAL services
+//!   (feature `service-generator`).
 //!
 //! ## Example
 //! ```rust
@@ -14,10 +16,15 @@
 
 use std::sync::Arc;
 use prost::Message;
-use rustecal_core::types::DataTypeInfo;
+use rustecal_core::types::{DataTypeInfo, EcalType};
 use rustecal_pubsub::typed_publisher::PublisherMessage;
 use rustecal_pubsub::typed_subscriber::SubscriberMessage;
 
+#[cfg(feature = "service-generator")]
+pub mod service_generator;
+#[cfg(feature = "service-generator")]
+pub use service_generator::EcalServiceGenerator;
+
 /// Marker trait to opt-in a Protobuf type for use with eCAL.
 ///
 /// This trait must be implemented for any `prost::Message` you wish to use
@@ -34,9 +41,9 @@ pub struct ProtobufMessage<T> {
     pub data: Arc<T>,
 }
 
-impl<T> SubscriberMessage for ProtobufMessage<T>
+impl<T> EcalType for ProtobufMessage<T>
 where
-    T: Message + Default + IsProtobufType,
+    T: Message + Default + IsProtobufType + 'static,
 {
     /// Returns metadata used by eCAL to describe the Protobuf type.
     ///
@@ -51,7 +58,12 @@ where
             descriptor: vec![], // descriptor injection planned
         }
     }
+}
 
+impl<T> SubscriberMessage for ProtobufMessage<T>
+where
+    T: Message + Default + IsProtobufType + 'static,
+{
     /// Decodes a Protobuf message from bytes.
     ///
     /// # Returns
@@ -66,13 +78,8 @@ where
 
 impl<T> PublisherMessage for ProtobufMessage<T>
 where
-    T: Message + Default + IsProtobufType,
+    T: Message + Default + IsProtobufType + 'static,
 {
-    /// Returns the same datatype information as [`SubscriberMessage`] implementation.
-    fn datatype() -> DataTypeInfo {
-        <ProtobufMessage<T> as SubscriberMessage>::datatype()
-    }
-
     /// Encodes the message to a byte buffer.
     ///
     /// # Panics
@@ -0,0 +1,199 @@
+//! A `prost-build` service generator for eCAL services.
+//!
+//! For every `service` in a `.proto` file, [`EcalServiceGenerator`] emits
+//!
+//! - a trait with one method per RPC, implemented by the server,
+//! - `<Service>Server::register`, which adds the methods to a `ServiceServer`,
+//! - `<Service>Client`, a typed wrapper around `ServiceClient`.
+//!
+//! Method names, type names and encoding follow the eCAL C++ protobuf
+//! services, so Rust and C++ servers and clients can call each other.
+//!
+//! ## Example (`build.rs`)
+//! ```rust,no_run
+//! use rustecal_types_protobuf::service_generator::EcalServiceGenerator;
+//!
+//! let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+//! prost_build::Config::new()
+//!     .file_descriptor_set_path(out_dir.join("descriptor.bin"))
+//!     .service_generator(Box::new(EcalServiceGenerator::new().with_descriptor_set("descriptor.bin")))
+//!     .compile_protos(&["proto/mirror.proto"], &["proto"])
+//!     .unwrap();
+//! ```
+
+use prost_build::{Method, Service, ServiceGenerator};
+use std::fmt::Write;
+
+/// Generates eCAL servers and clients for protobuf services.
+///
+/// The generated code uses `rustecal` (feature `service`) and `prost`, which
+/// must be dependencies of the crate including it.
+#[derive(Debug, Clone, Default)]
+pub struct EcalServiceGenerator {
+    descriptor_set: Option<String>,
+}
+
+impl EcalServiceGenerator {
+    /// Creates a generator that announces types without descriptors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Embeds the file descriptor set written by
+    /// `prost_build::Config::file_descriptor_set_path` as descriptor of all
+    /// request and response types.
+    ///
+    /// `file_name` is relative to `OUT_DIR`.
+    pub fn with_descriptor_set(mut self, file_name: &str) -> Self {
+        self.descriptor_set = Some(file_name.to_string());
+        self
+    }
+}
+
+impl ServiceGenerator for EcalServiceGenerator {
+    fn generate(&mut self, service: Service, buf: &mut String) {
+        let full_name = if service.package.is_empty() {
+            service.proto_name.clone()
+        } else {
+            format!("{}.{}", service.package, service.proto_name)
+        };
+        let descriptor = match &self.descriptor_set {
+            Some(file) => format!(
+                "::std::include_bytes!(::std::concat!(::std::env!(\"OUT_DIR\"), \"/{}\"))",
+                file
+            ),
+            None => "&[]".to_string(),
+        };
+
+        let (methods, streaming): (Vec<&Method>, Vec<&Method>) = service
+            .methods
+            .iter()
+            .partition(|m| !m.client_streaming && !m.server_streaming);
+
+        let name = &service.name;
+        let server = format!("{}Server", name);
+        let client = format!("{}Client", name);
+
+        // Trait
+        service.comments.append_with_indent(0, buf);
+        let _ = writeln!(buf, "pub trait {} {{", name);
+        for method in &methods {
+            method.comments.append_with_indent(1, buf);
+            let _ = writeln!(
+                buf,
+                "    fn {}(&self, request: {}) -> ::std::result::Result<{}, ::rustecal::__private::ServiceError>;",
+                method.name, method.input_type, method.output_type
+            );
+        }
+        for method in &streaming {
+            let _ = writeln!(
+                buf,
+                "    // `{}` streams messages, which eCAL services do not support",
+                method.proto_name
+            );
+        }
+        buf.push_str("}\n\n");
+
+        // Server
+        let _ = writeln!(buf, "/// Registers implementations of [`{}`] on a `ServiceServer`.", name);
+        let _ = writeln!(buf, "pub struct {};\n", server);
+        let _ = writeln!(buf, "impl {} {{", server);
+        let _ = writeln!(buf, "    /// The fully qualified service name, as used by eCAL C++ protobuf services.");
+        let _ = writeln!(buf, "    pub const SERVICE_NAME: &'static str = \"{}\";\n", full_name);
+        let _ = writeln!(buf, "    /// Descriptor announced for the request and response types.");
+        let _ = writeln!(buf, "    pub const DESCRIPTOR: &'static [u8] = {};\n", descriptor);
+        let _ = writeln!(buf, "    /// Adds all methods to `server`, dispatching to `service`.");
+        let _ = writeln!(buf, "    pub fn register<S>(server: &::rustecal::__private::ServiceServer, service: S) -> ::std::result::Result<(), ::std::string::String>");
+        let _ = writeln!(buf, "    where");
+        let _ = writeln!(buf, "        S: {} + ::std::marker::Send + ::std::marker::Sync + 'static,", name);
+        let _ = writeln!(buf, "    {{");
+        let _ = writeln!(buf, "        let service = ::std::sync::Arc::new(service);");
+        for method in &methods {
+            let _ = writeln!(buf, "        {{");
+            let _ = writeln!(buf, "            let service = service.clone();");
+            let _ = writeln!(buf, "            server.add_typed_method(");
+            let _ = writeln!(buf, "                \"{}\",", method.proto_name);
+            let _ = writeln!(buf, "                &{},", datatype(&server, &method.input_proto_type));
+            let _ = writeln!(buf, "                &{},", datatype(&server, &method.output_proto_type));
+            let _ = writeln!(buf, "                ::std::boxed::Box::new(move |_info, request: &[u8]| {{");
+            let _ = writeln!(buf, "                    let request = <{} as ::prost::Message>::decode(request).map_err(|e| {{", method.input_type);
+            let _ = writeln!(buf, "                        ::rustecal::__private::ServiceError::new(::std::format!(\"failed to decode request of method '{}': {{}}\", e))", method.proto_name);
+            let _ = writeln!(buf, "                    }})?;");
+            let _ = writeln!(buf, "                    let response = service.{}(request)?;", method.name);
+            let _ = writeln!(buf, "                    Ok(::prost::Message::encode_to_vec(&response))");
+            let _ = writeln!(buf, "                }}),");
+            let _ = writeln!(buf, "            )?;");
+            let _ = writeln!(buf, "        }}");
+        }
+        let _ = writeln!(buf, "        Ok(())");
+        let _ = writeln!(buf, "    }}");
+        buf.push_str("}\n\n");
+
+        // Client
+        let _ = writeln!(buf, "/// Typed client for the `{}` service.", full_name);
+        let _ = writeln!(buf, "pub struct {} {{", client);
+        let _ = writeln!(buf, "    client: ::rustecal::__private::ServiceClient,");
+        let _ = writeln!(buf, "    timeout_ms: ::std::option::Option<i32>,");
+        buf.push_str("}\n\n");
+        let _ = writeln!(buf, "impl {} {{", client);
+        let _ = writeln!(buf, "    /// Creates a client for `service_name`, declaring all methods with their types.");
+        let _ = writeln!(buf, "    ///");
+        let _ = writeln!(buf, "    /// Use [`{}::SERVICE_NAME`] to reach C++ servers. Calls time out after", server);
+        let _ = writeln!(buf, "    /// 1000 ms unless changed with `with_timeout`.");
+        let _ = writeln!(buf, "    pub fn new(service_name: &str) -> ::std::result::Result<Self, ::std::string::String> {{");
+        let _ = writeln!(buf, "        let client = ::rustecal::__private::ServiceClient::with_methods(");
+        let _ = writeln!(buf, "            service_name,");
+        let _ = writeln!(buf, "            ::std::vec![");
+        for method in &methods {
+            let _ = writeln!(
+                buf,
+                "                ::rustecal::__private::ClientMethod::with_types(\"{}\", {}, {}),",
+                method.proto_name,
+                datatype(&server, &method.input_proto_type),
+                datatype(&server, &method.output_proto_type)
+            );
+        }
+        let _ = writeln!(buf, "            ],");
+        let _ = writeln!(buf, "        )?;");
+        let _ = writeln!(buf, "        Ok(Self {{ client, timeout_ms: Some(1000) }})");
+        let _ = writeln!(buf, "    }}\n");
+        let _ = writeln!(buf, "    /// Sets the call timeout in milliseconds; `None` waits indefinitely.");
+        let _ = writeln!(buf, "    pub fn with_timeout(mut self, timeout_ms: ::std::option::Option<i32>) -> Self {{");
+        let _ = writeln!(buf, "        self.timeout_ms = timeout_ms;");
+        let _ = writeln!(buf, "        self");
+        let _ = writeln!(buf, "    }}\n");
+        let _ = writeln!(buf, "    /// Returns the underlying untyped client.");
+        let _ = writeln!(buf, "    pub fn client(&self) -> &::rustecal::__private::ServiceClient {{");
+        let _ = writeln!(buf, "        &self.client");
+        let _ = writeln!(buf, "    }}");
+        for method in &methods {
+            buf.push('\n');
+            method.comments.append_with_indent(1, buf);
+            let _ = writeln!(buf, "    pub fn {}(&self, request: &{}) -> ::std::result::Result<{}, ::rustecal::__private::CallError> {{", method.name, method.input_type, method.output_type);
+            let _ = writeln!(buf, "        let request = ::rustecal::__private::ServiceRequest {{ payload: ::prost::Message::encode_to_vec(request) }};");
+            let _ = writeln!(buf, "        let responses = self.client.call(\"{}\", request, self.timeout_ms)?;", method.proto_name);
+            let _ = writeln!(buf, "        match responses.iter().find(|r| r.is_success()) {{");
+            let _ = writeln!(buf, "            Some(success) => <{} as ::prost::Message>::decode(success.payload.as_slice()).map_err(|e| {{", method.output_type);
+            let _ = writeln!(buf, "                ::rustecal::__private::CallError::Failed(::std::format!(\"failed to decode response of method '{}': {{}}\", e))", method.proto_name);
+            let _ = writeln!(buf, "            }}),");
+            let _ = writeln!(buf, "            None => Err(::rustecal::__private::CallError::Failed(");
+            let _ = writeln!(buf, "                responses.into_iter().find_map(|r| r.error_msg).unwrap_or_else(|| \"call failed\".into()),");
+            let _ = writeln!(buf, "            )),");
+            let _ = writeln!(buf, "        }}");
+            let _ = writeln!(buf, "    }}");
+        }
+        buf.push_str("}\n");
+    }
+}
+
+/// Expression building the `DataTypeInfo` of the protobuf type `proto_type`.
+///
+/// `proto_type` is fully qualified with a leading dot, e.g. `.pkg.Request`;
+/// eCAL names protobuf types without it.
+fn datatype(server: &str, proto_type: &str) -> String {
+    format!(
+        "::rustecal::__private::DataTypeInfo {{ encoding: \"proto\".to_string(), type_name: \"{}\".to_string(), descriptor: {}::DESCRIPTOR.to_vec() }}",
+        proto_type.trim_start_matches('.'),
+        server
+    )
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use serde::{Deserialize, Serialize};
-use rustecal_core::types::DataTypeInfo;
+use rustecal_core::types::{DataTypeInfo, EcalType};
 use rustecal_pubsub::typed_publisher::PublisherMessage;
 use rustecal_pubsub::typed_subscriber::SubscriberMessage;
 use crate::format_support::{FormatSupport, short_type_name};
@@ -21,20 +21,23 @@ impl FormatSupport for MsgpackSupport {
 
 make_format!(MsgpackMessage, MsgpackSupport);
 
-impl<T> PublisherMessage for MsgpackMessage<T>
-where T: Serialize + for<'de> Deserialize<'de> + Clone
+impl<T> EcalType for MsgpackMessage<T>
+where T: Serialize + for<'de> Deserialize<'de> + Clone + 'static
 {
     fn datatype() -> DataTypeInfo {
         DataTypeInfo { encoding: MsgpackSupport::ENCODING.into(), type_name: short_type_name::<T>(), descriptor: vec![] }
     }
+}
+impl<T> PublisherMessage for MsgpackMessage<T>
+where T: Serialize + for<'de> Deserialize<'de> + Clone + 'static
+{
     fn to_bytes(&self) -> Arc<[u8]> {
         Arc::from(MsgpackSupport::encode(&*self.data))
     }
 }
 impl<T> SubscriberMessage for MsgpackMessage<T>
-where T: Serialize + for<'de> Deserialize<'de> + Clone
+where T: Serialize + for<'de> Deserialize<'de> + Clone + 'static
 {
-    fn datatype() -> DataTypeInfo { <MsgpackMessage<T> as PublisherMessage>::datatype() }
     fn from_bytes(bytes: Arc<[u8]>, _dt: &DataTypeInfo) -> Option<Self> {
         MsgpackSupport::decode(bytes.as_ref()).map(|p| MsgpackMessage { data: Arc::new(p) })
     }
@@ -11,7 +11,7 @@
 
 use std::str;
 use std::sync::Arc;
-use rustecal_core::types::DataTypeInfo;
+use rustecal_core::types::{DataTypeInfo, EcalType};
 use rustecal_pubsub::typed_publisher::PublisherMessage;
 use rustecal_pubsub::typed_subscriber::SubscriberMessage;
 
@@ -23,7 +23,7 @@ pub struct StringMessage {
     pub data: Arc<str>,
 }
 
-impl SubscriberMessage for StringMessage {
+impl EcalType for StringMessage {
     /// Returns metadata describing this message type (`utf-8` encoded string).
     fn datatype() -> DataTypeInfo {
         DataTypeInfo {
@@ -32,7 +32,9 @@ impl SubscriberMessage for StringMessage {
             descriptor: vec![],
         }
     }
+}
 
+impl SubscriberMessage for StringMessage {
     /// Attempts to decode a UTF-8 string from a byte buffer.
     fn from_bytes(bytes: Arc<[u8]>, _data_type_info: &DataTypeInfo) -> Option<Self> {
         str::from_utf8(bytes.as_ref())
@@ -42,11 +44,6 @@ impl SubscriberMessage for StringMessage {
 }
 
 impl PublisherMessage for StringMessage {
-    /// Returns the same metadata as [`SubscriberMessage::datatype`].
-    fn datatype() -> DataTypeInfo {
-        <StringMessage as SubscriberMessage>::datatype()
-    }
-
     /// Serializes the string into a byte buffer.
     fn to_bytes(&self) -> Arc<[u8]> {
         Arc::from(self.data.as_bytes())
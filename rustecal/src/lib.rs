@@ -7,6 +7,7 @@
 //! - `core`: Initialization and runtime management.
 //! - `pubsub`: Typed publishers and subscribers.
 //! - `service`: Synchronous RPC communication.
+//! - `rpc`: Typed services generated from traits (`macros` feature).
 //!
 //! ## Example
 //! ```rust
@@ -24,7 +25,8 @@
 
 // —————————————————————————————————————————————————————————————————————————————
 // Core initialization & types (always available)
-pub use rustecal_core::{Ecal, EcalComponents};
+pub use rustecal_core::{Ecal, EcalComponents, EcalGuard};
+pub use rustecal_core::types::{DataTypeInfo, EcalType};
 
 // —————————————————————————————————————————————————————————————————————————————
 // Pub/Sub API (requires the `pubsub` feature)
@@ -37,10 +39,12 @@ pub mod pubsub {
 #[cfg(feature = "pubsub")]
 pub use rustecal_pubsub::{
     // low‑level handles
-    Publisher, Subscriber,
+    Publisher, Subscriber, RawReceived,
     // typed wrappers
     TypedPublisher, PublisherMessage,
     TypedSubscriber, SubscriberMessage,
+    // pattern subscriptions
+    TopicWatcher, TopicPattern,
 };
 
 // —————————————————————————————————————————————————————————————————————————————
@@ -54,13 +58,39 @@ pub mod service {
 #[cfg(feature = "service")]
 pub use rustecal_service::{
     // server & client entrypoints
-    ServiceServer, ServiceClient, ClientInstance,
+    ServiceServer, ServiceClient, ClientInstance, CallStrategy, RetryPolicy, RetryOn,
     // request/response types
-    ServiceRequest, ServiceResponse,
+    ServiceRequest, ServiceResponse, ServiceId, ServiceError, ClientMethod, CallError,
+    // client events
+    ClientEvent, ClientEventKind,
 };
 
+#[cfg(feature = "async")]
+pub use rustecal_service::AsyncMethodOptions;
+
+#[cfg(feature = "streaming")]
+pub use rustecal_service::{ResponseStream, StreamError, StreamOptions, StreamSink};
+
 #[cfg(feature = "service")]
 pub use rustecal_service::types::{
     // metadata & callback signature
     MethodInfo, ServiceCallback, CallState,
 };
+
+// —————————————————————————————————————————————————————————————————————————————
+// Procedural macros (requires the `macros` feature)
+#[cfg(feature = "macros")]
+pub use rustecal_macros::{rpc, EcalMessage};
+
+/// Items used by code generated by `rustecal-macros` and the protobuf
+/// service generator. Not a public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use rustecal_core::types::{DataTypeInfo, EcalType};
+    #[cfg(feature = "pubsub")]
+    pub use rustecal_pubsub::{PublisherMessage, SubscriberMessage};
+    #[cfg(feature = "service")]
+    pub use rustecal_service::{
+        CallError, ClientMethod, ServiceClient, ServiceError, ServiceRequest, ServiceServer,
+    };
+}